@@ -0,0 +1,27 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tag_password::{generate_salt, Argon2Profile, Password, Plain};
+
+fn hashing(c: &mut Criterion) {
+    let plain_password: Password<Plain> = Password::new("Password");
+    let salt = generate_salt();
+
+    for (name, profile) in [
+        ("hash/default", Argon2Profile::Default),
+        ("hash/testing", Argon2Profile::Testing),
+    ] {
+        let argon2 = profile.argon2();
+        c.bench_function(name, |b| {
+            b.iter(|| plain_password.hash_shared(&argon2, &salt))
+        });
+    }
+
+    let hashed_password = plain_password
+        .hash_fast(&salt)
+        .expect("Argon2 encoding should not fail.");
+    c.bench_function("verify/testing", |b| {
+        b.iter(|| hashed_password.is_same_as(None, &plain_password))
+    });
+}
+
+criterion_group!(benches, hashing);
+criterion_main!(benches);
@@ -0,0 +1,506 @@
+//! Analysis helpers inspecting plain text passwords without exposing their value.
+
+use crate::{Password, Plain};
+use std::{borrow::Cow, collections::HashSet};
+
+/// Keyboard layout used to detect keyboard walks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// The US and UK QWERTY layout.
+    #[default]
+    Qwerty,
+    /// The French AZERTY layout.
+    Azerty,
+}
+
+/// Set of dictionary words used to estimate the strength of passwords built from them.
+///
+/// Words are compared case-insensitively. Words shorter than three characters are ignored, as
+/// they would match fragments of almost any password.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    words: HashSet<String>,
+    longest: usize,
+}
+
+impl Dictionary {
+    /// Creates a new `Dictionary` from the provided words.
+    pub fn new<W: AsRef<str>>(words: impl IntoIterator<Item = W>) -> Self {
+        let words: HashSet<String> = words
+            .into_iter()
+            .map(|word| word.as_ref().to_lowercase())
+            .filter(|word| word.chars().count() >= 3)
+            .collect();
+        let longest = words
+            .iter()
+            .map(|word| word.chars().count())
+            .max()
+            .unwrap_or(0);
+        Dictionary { words, longest }
+    }
+
+    /// Returns the number of words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns `true` if the dictionary contains the word, ignoring case.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+}
+
+impl KeyboardLayout {
+    /// Retrieves the rows of unshifted keys of the layout, from the number row down.
+    fn rows(&self) -> [&'static str; 4] {
+        match self {
+            KeyboardLayout::Qwerty => ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            KeyboardLayout::Azerty => ["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn"],
+        }
+    }
+
+    /// Locates the row and column of a character on the layout.
+    fn position(&self, c: char) -> Option<(usize, usize)> {
+        self.rows()
+            .iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.chars().position(|key| key == c).map(|col| (row, col)))
+    }
+
+    /// Checks whether two keys are next to each other on the staggered layout.
+    fn adjacent(&self, a: char, b: char) -> bool {
+        let (Some((row_a, col_a)), Some((row_b, col_b))) = (self.position(a), self.position(b))
+        else {
+            return false;
+        };
+        match row_a.abs_diff(row_b) {
+            0 => col_a.abs_diff(col_b) == 1,
+            1 => {
+                let (upper, lower) = match row_a < row_b {
+                    true => (col_a, col_b),
+                    false => (col_b, col_a),
+                };
+                lower == upper || lower + 1 == upper
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Password<Plain> {
+    /// Iterates over the characters of the password, for custom analysis without copying its
+    /// value out.
+    ///
+    /// Returns an iterator over the characters of the password.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.1.chars()
+    }
+
+    /// Iterates over the UTF-8 bytes of the password, for custom analysis without copying its
+    /// value out.
+    ///
+    /// Returns an iterator over the bytes of the password.
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.1.bytes()
+    }
+
+    /// Counts the whitespace separated words of the password, such as a diceware passphrase.
+    ///
+    /// Returns the number of words in the password.
+    pub fn word_count(&self) -> usize {
+        self.1.split_whitespace().count()
+    }
+
+    /// Computes the ratio of distinct words to total words in the password.
+    ///
+    /// A value of `1.0` means every word is unique, while lower values indicate repeated
+    /// words. An empty password has a ratio of `0.0`.
+    ///
+    /// Returns the ratio as a value between `0.0` and `1.0`.
+    pub fn unique_word_ratio(&self) -> f64 {
+        let words = self.word_count();
+        if words == 0 {
+            return 0.0;
+        }
+
+        let unique = self.1.split_whitespace().collect::<HashSet<_>>().len();
+        unique as f64 / words as f64
+    }
+
+    /// Checks whether the password repeats the same character consecutively, such as `"aaaa"`.
+    ///
+    /// - `threshold`: The number of consecutive identical characters considered weak.
+    ///
+    /// Returns `true` if a run of at least `threshold` identical characters is found.
+    pub fn has_repeated_chars(&self, threshold: usize) -> bool {
+        self.has_run(threshold, |previous, current| previous == current)
+    }
+
+    /// Checks whether the password contains a run of sequential characters, such as `"1234"`,
+    /// `"abcd"` or `"dcba"`.
+    ///
+    /// - `threshold`: The number of sequential characters considered weak.
+    ///
+    /// Returns `true` if a run of at least `threshold` ascending or descending characters is
+    /// found.
+    pub fn has_sequential_run(&self, threshold: usize) -> bool {
+        self.has_run(threshold, |previous, current| {
+            (previous as u32).checked_add(1) == Some(current as u32)
+        }) || self.has_run(threshold, |previous, current| {
+            (previous as u32).checked_sub(1) == Some(current as u32)
+        })
+    }
+
+    /// Checks whether the password contains any of the provided values, such as a username or
+    /// the local part of an email address.
+    ///
+    /// - `needles`: The values the password must not contain. Empty values are ignored.
+    /// - `case_insensitive`: Whether the comparison ignores case.
+    ///
+    /// Returns `true` if the password contains at least one of the values.
+    pub fn contains_any_of(&self, needles: &[&str], case_insensitive: bool) -> bool {
+        let haystack = match case_insensitive {
+            true => Cow::Owned(self.1.to_lowercase()),
+            false => Cow::Borrowed(self.1.as_str()),
+        };
+        needles
+            .iter()
+            .filter(|needle| !needle.is_empty())
+            .any(|needle| match case_insensitive {
+                true => haystack.contains(&needle.to_lowercase()),
+                false => haystack.contains(needle),
+            })
+    }
+
+    /// Checks whether the password differs from a previous password by enough edits.
+    ///
+    /// - `old`: The previous password, such as the one being replaced in a password change.
+    /// - `min_distance`: The minimum number of inserted, removed or substituted characters.
+    ///
+    /// The Levenshtein distance is computed over characters and is case-sensitive.
+    ///
+    /// Returns `true` if at least `min_distance` edits turn `old` into this password.
+    pub fn differs_enough_from(&self, old: &Password<Plain>, min_distance: usize) -> bool {
+        levenshtein(&self.1, &old.1) >= min_distance
+    }
+
+    /// Checks whether the password is a keyboard walk, such as `qwerty` or `asdf`.
+    ///
+    /// - `layout`: The keyboard layout to check against.
+    ///
+    /// A keyboard walk is a password of at least three characters where every character is
+    /// next to the previous one on the layout, horizontally or diagonally. Letters are
+    /// compared case-insensitively.
+    ///
+    /// Returns `true` if the whole password is a keyboard walk.
+    pub fn is_keyboard_walk(&self, layout: KeyboardLayout) -> bool {
+        let chars: Vec<char> = self.1.to_lowercase().chars().collect();
+        chars.len() >= 3
+            && chars
+                .windows(2)
+                .all(|pair| layout.adjacent(pair[0], pair[1]))
+    }
+
+    /// Returns `true` if the password is non-empty and only contains numeric characters, such
+    /// as a PIN.
+    pub fn is_numeric_only(&self) -> bool {
+        !self.1.is_empty() && self.1.chars().all(char::is_numeric)
+    }
+
+    /// Returns `true` if the password only contains ASCII characters, which is the case of an
+    /// empty password.
+    pub fn is_ascii(&self) -> bool {
+        self.1.is_ascii()
+    }
+
+    /// Returns `true` if the password is non-empty and only contains alphabetic or numeric
+    /// characters.
+    pub fn is_alphanumeric_only(&self) -> bool {
+        !self.1.is_empty() && self.1.chars().all(char::is_alphanumeric)
+    }
+
+    /// Estimates the entropy of the password from the character classes it uses.
+    ///
+    /// The estimate assumes every character was picked uniformly from the union of the classes
+    /// present in the password: lowercase and uppercase ASCII letters, digits, ASCII symbols and
+    /// any other character. It overestimates passwords built from words or patterns.
+    ///
+    /// Returns the estimated entropy in bits.
+    pub fn entropy_bits(&self) -> f64 {
+        self.1.chars().count() as f64 * charset_size(&self.1).log2()
+    }
+
+    /// Estimates the entropy of the password, accounting for dictionary words it contains.
+    ///
+    /// - `dictionary`: The words an attacker is assumed to try, such as a list of common
+    ///   passwords.
+    ///
+    /// The password is scanned for the longest dictionary word at each position. Each word found
+    /// counts as a single pick from the dictionary, while the remaining characters count as in
+    /// `entropy_bits`. The result never exceeds `entropy_bits`.
+    ///
+    /// Returns the estimated entropy in bits.
+    pub fn effective_entropy_bits(&self, dictionary: &Dictionary) -> f64 {
+        let chars: Vec<char> = self.1.to_lowercase().chars().collect();
+        let word_bits = (dictionary.len().max(1) as f64).log2();
+        let char_bits = charset_size(&self.1).log2();
+
+        let mut bits = 0.0;
+        let mut start = 0;
+        while start < chars.len() {
+            let longest = dictionary.longest.min(chars.len() - start);
+            match (3..=longest).rev().find(|len| {
+                dictionary
+                    .words
+                    .contains(&chars[start..start + len].iter().collect::<String>())
+            }) {
+                Some(len) => {
+                    bits += word_bits;
+                    start += len;
+                }
+                None => {
+                    bits += char_bits;
+                    start += 1;
+                }
+            }
+        }
+        bits.min(self.entropy_bits())
+    }
+
+    /// Masks the password for echoing it in a user interface, one character per typed
+    /// character.
+    ///
+    /// - `ch`: The character shown in place of each character, such as `'•'`.
+    ///
+    /// The length is counted in characters rather than bytes, so multibyte characters are
+    /// masked by a single character.
+    ///
+    /// Returns a string repeating `ch` as many times as the password has characters.
+    pub fn mask_preserving_length(&self, ch: char) -> String {
+        std::iter::repeat_n(ch, self.1.chars().count()).collect()
+    }
+
+    /// Checks whether consecutive characters satisfy `follows` for at least `threshold`
+    /// characters in a row.
+    fn has_run(&self, threshold: usize, follows: impl Fn(char, char) -> bool) -> bool {
+        let mut previous = None;
+        let mut run = 0;
+        for current in self.1.chars() {
+            run = match previous {
+                Some(previous) if follows(previous, current) => run + 1,
+                _ => 1,
+            };
+            if run >= threshold {
+                return true;
+            }
+            previous = Some(current);
+        }
+        false
+    }
+}
+
+/// Computes the number of characters in the classes used by the value.
+fn charset_size(value: &str) -> f64 {
+    let mut classes = [false; 5];
+    for c in value.chars() {
+        let class = match c {
+            'a'..='z' => 0,
+            'A'..='Z' => 1,
+            '0'..='9' => 2,
+            c if c.is_ascii_punctuation() => 3,
+            _ => 4,
+        };
+        classes[class] = true;
+    }
+    let size: f64 = [26.0, 26.0, 10.0, 32.0, 100.0]
+        .iter()
+        .zip(classes)
+        .filter(|(_, used)| *used)
+        .map(|(size, _)| size)
+        .sum();
+    size.max(1.0)
+}
+
+/// Computes the Levenshtein distance between two strings, counted in characters.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dictionary, KeyboardLayout};
+    use crate::{Password, Plain};
+
+    #[test]
+    fn passphrase_word_statistics() {
+        let passphrase: Password<Plain> =
+            Password::new("correct horse battery staple horse correct");
+
+        assert_eq!(passphrase.word_count(), 6);
+        assert!((passphrase.unique_word_ratio() - 4.0 / 6.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn weak_pattern_detection() {
+        let repeated: Password<Plain> = Password::new("xaaaay");
+        let sequential: Password<Plain> = Password::new("abcdef");
+        let random: Password<Plain> = Password::new("q7Rk2vLm9X");
+
+        assert!(repeated.has_repeated_chars(4));
+        assert!(!repeated.has_repeated_chars(5));
+        assert!(!repeated.has_sequential_run(3));
+
+        assert!(sequential.has_sequential_run(6));
+        assert!(!sequential.has_sequential_run(7));
+        assert!(!sequential.has_repeated_chars(2));
+        assert!(Password::<Plain>::new("x4321y").has_sequential_run(4));
+
+        assert!(!random.has_repeated_chars(2));
+        assert!(!random.has_sequential_run(3));
+    }
+
+    #[test]
+    fn user_info_detection() {
+        let plain_password: Password<Plain> = Password::new("MyJohnDoe2024!");
+
+        assert!(plain_password.contains_any_of(&["johndoe", "jd@example.com"], true));
+        assert!(!plain_password.contains_any_of(&["johndoe"], false));
+        assert!(plain_password.contains_any_of(&["JohnDoe"], false));
+        assert!(!plain_password.contains_any_of(&["alice", ""], true));
+        assert!(!plain_password.contains_any_of(&[], true));
+    }
+
+    #[test]
+    fn keyboard_walk_detection() {
+        let walk = |value: &str, layout| Password::<Plain>::new(value).is_keyboard_walk(layout);
+
+        assert!(walk("qwerty", KeyboardLayout::Qwerty));
+        assert!(walk("ASDF", KeyboardLayout::Qwerty));
+        assert!(walk("fdsa", KeyboardLayout::Qwerty));
+        assert!(!walk("qazwsx", KeyboardLayout::Qwerty));
+        assert!(walk("1qaz", KeyboardLayout::Qwerty));
+        assert!(!walk("q7Rk2vLm9X", KeyboardLayout::Qwerty));
+        assert!(!walk("qw", KeyboardLayout::Qwerty));
+
+        assert!(walk("azerty", KeyboardLayout::Azerty));
+        assert!(!walk("azerty", KeyboardLayout::Qwerty));
+        assert!(walk("qsdf", KeyboardLayout::Azerty));
+        assert!(!walk("qwerty", KeyboardLayout::Azerty));
+    }
+
+    #[test]
+    fn change_distance() {
+        let old: Password<Plain> = Password::new("Summer2023!");
+
+        assert!(!Password::<Plain>::new("Summer2023!").differs_enough_from(&old, 1));
+        assert!(Password::<Plain>::new("Summer2023!").differs_enough_from(&old, 0));
+        assert!(Password::<Plain>::new("Summer2024!").differs_enough_from(&old, 1));
+        assert!(!Password::<Plain>::new("Summer2024!").differs_enough_from(&old, 2));
+        assert!(Password::<Plain>::new("q7Rk2vLm9X").differs_enough_from(&old, 8));
+        assert_eq!(super::levenshtein("kitten", "sitting"), 3);
+        assert_eq!(super::levenshtein("", "äbc"), 3);
+    }
+
+    #[test]
+    fn dictionary_words_reduce_entropy() {
+        let dictionary = Dictionary::new(["sunshine", "dragon", "monkey", "at"]);
+        let word: Password<Plain> = Password::new("sunshine");
+        let random: Password<Plain> = Password::new("qzvkfjwp");
+
+        assert_eq!(dictionary.len(), 3);
+        assert!(dictionary.contains("DRAGON"));
+        assert!((word.entropy_bits() - random.entropy_bits()).abs() < f64::EPSILON);
+        assert!(word.effective_entropy_bits(&dictionary) < 2.0);
+        assert!(
+            (random.effective_entropy_bits(&dictionary) - random.entropy_bits()).abs()
+                < f64::EPSILON
+        );
+        assert!(
+            Password::<Plain>::new("Dragon42").effective_entropy_bits(&dictionary)
+                < Password::<Plain>::new("Dragon42").entropy_bits() / 2.0
+        );
+        assert_eq!(Password::<Plain>::new("").entropy_bits(), 0.0);
+    }
+
+    #[test]
+    fn character_set_predicates() {
+        let digits: Password<Plain> = Password::new("123456");
+        let unicode: Password<Plain> = Password::new("mötörhead");
+        let mixed: Password<Plain> = Password::new("Pass word1!");
+
+        assert!(digits.is_numeric_only());
+        assert!(digits.is_ascii());
+        assert!(digits.is_alphanumeric_only());
+
+        assert!(!unicode.is_numeric_only());
+        assert!(!unicode.is_ascii());
+        assert!(unicode.is_alphanumeric_only());
+
+        assert!(!mixed.is_numeric_only());
+        assert!(mixed.is_ascii());
+        assert!(!mixed.is_alphanumeric_only());
+
+        assert!(!Password::<Plain>::new("").is_numeric_only());
+        assert!(!Password::<Plain>::new("").is_alphanumeric_only());
+    }
+
+    #[test]
+    fn mask_counts_characters() {
+        assert_eq!(
+            Password::<Plain>::new("Password").mask_preserving_length('•'),
+            "••••••••"
+        );
+        assert_eq!(
+            Password::<Plain>::new("pa🔑ß").mask_preserving_length('*'),
+            "****"
+        );
+        assert_eq!(Password::<Plain>::new("").mask_preserving_length('*'), "");
+    }
+
+    #[test]
+    fn single_word_statistics() {
+        let password: Password<Plain> = Password::new("Password");
+        let empty: Password<Plain> = Password::new("");
+
+        assert_eq!(password.word_count(), 1);
+        assert_eq!(password.unique_word_ratio(), 1.0);
+        assert_eq!(empty.word_count(), 0);
+        assert_eq!(empty.unique_word_ratio(), 0.0);
+    }
+
+    #[test]
+    fn iterators_expose_characters_and_bytes() {
+        let password: Password<Plain> = Password::new("pa\u{df}\u{1F511}");
+
+        assert_eq!(
+            password.chars().map(u32::from).sum::<u32>(),
+            0x70 + 0x61 + 0xDF + 0x1F511
+        );
+        assert_eq!(password.chars().count(), 4);
+        assert_eq!(
+            password.bytes().map(u32::from).sum::<u32>(),
+            password
+                .as_bytes()
+                .iter()
+                .map(|&b| u32::from(b))
+                .sum::<u32>()
+        );
+        assert_eq!(password.bytes().count(), 8);
+    }
+}
@@ -0,0 +1,146 @@
+//! Diagnostic tracking of how long plain text passwords stay in memory.
+//!
+//! With the `lifetime-audit` feature enabled, debug builds record when each
+//! `Password<Plain>` is created and log a warning through `tracing` when it is dropped after
+//! living longer than the configured threshold. This is meant to find plain text passwords
+//! held for too long during security reviews, not as production behavior.
+
+#[cfg(feature = "lifetime-audit")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "lifetime-audit")]
+use std::time::Duration;
+#[cfg(all(feature = "lifetime-audit", debug_assertions))]
+use std::time::Instant;
+
+/// Lifetime above which a plain text password is reported, in milliseconds.
+#[cfg(feature = "lifetime-audit")]
+static THRESHOLD_MILLIS: AtomicU64 = AtomicU64::new(30_000);
+
+/// Sets the lifetime above which dropping a plain text password logs a warning.
+///
+/// - `threshold`: The maximum expected lifetime of a plain text password.
+///
+/// The default threshold is 30 seconds.
+#[cfg(feature = "lifetime-audit")]
+pub fn set_lifetime_threshold(threshold: Duration) {
+    let millis = u64::try_from(threshold.as_millis()).unwrap_or(u64::MAX);
+    THRESHOLD_MILLIS.store(millis, Ordering::Relaxed);
+}
+
+/// Retrieves the lifetime above which dropping a plain text password logs a warning.
+#[cfg(feature = "lifetime-audit")]
+pub fn lifetime_threshold() -> Duration {
+    Duration::from_millis(THRESHOLD_MILLIS.load(Ordering::Relaxed))
+}
+
+/// Records the creation time of a plain text password and reports its lifetime on drop.
+///
+/// This is a zero-sized type unless the `lifetime-audit` feature is enabled in a debug build.
+pub(crate) struct Audit {
+    #[cfg(all(feature = "lifetime-audit", debug_assertions))]
+    created: Option<Instant>,
+}
+
+impl Audit {
+    /// Starts auditing a password with the marker `T`, recording only plain text passwords.
+    #[cfg_attr(
+        not(all(feature = "lifetime-audit", debug_assertions)),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    pub(crate) fn start<T: crate::sealed::Marker + ?Sized>() -> Self {
+        Audit {
+            #[cfg(all(feature = "lifetime-audit", debug_assertions))]
+            created: T::PLAIN.then(Instant::now),
+        }
+    }
+}
+
+impl Clone for Audit {
+    /// Starts auditing the clone as a new password.
+    fn clone(&self) -> Self {
+        Audit {
+            #[cfg(all(feature = "lifetime-audit", debug_assertions))]
+            created: self.created.map(|_| Instant::now()),
+        }
+    }
+}
+
+#[cfg(all(feature = "lifetime-audit", debug_assertions))]
+impl Drop for Audit {
+    /// Logs a warning if the plain text password outlived the configured threshold.
+    fn drop(&mut self) {
+        if let Some(created) = self.created {
+            let lifetime = created.elapsed();
+            if lifetime > lifetime_threshold() {
+                tracing::warn!(
+                    lifetime_ms = lifetime.as_millis() as u64,
+                    "plain text password outlived the audit threshold"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "lifetime-audit", debug_assertions))]
+mod tests {
+    use super::set_lifetime_threshold;
+    use crate::{Hashed, Password, Plain};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread::sleep,
+        time::Duration,
+    };
+    use tracing::{
+        span::{Attributes, Id, Record},
+        Event, Level, Metadata, Subscriber,
+    };
+
+    /// Subscriber counting the warnings emitted while it is active.
+    struct WarningCounter(Arc<AtomicUsize>);
+
+    impl Subscriber for WarningCounter {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            if *event.metadata().level() == Level::WARN {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn long_lived_plaintext_is_reported() {
+        let warnings = Arc::new(AtomicUsize::new(0));
+        set_lifetime_threshold(Duration::from_millis(50));
+
+        tracing::subscriber::with_default(WarningCounter(warnings.clone()), || {
+            drop(Password::<Plain>::new("short"));
+            assert_eq!(warnings.load(Ordering::SeqCst), 0);
+
+            let hashed_password = Password::<Hashed>::new_unchecked("hash");
+            let plain_password = Password::<Plain>::new("long");
+            sleep(Duration::from_millis(100));
+            drop(hashed_password);
+            assert_eq!(warnings.load(Ordering::SeqCst), 0);
+            drop(plain_password);
+            assert_eq!(warnings.load(Ordering::SeqCst), 1);
+        });
+    }
+}
@@ -0,0 +1,304 @@
+//! Pluggable hashing backends.
+//!
+//! The `PasswordHasher` and `PasswordVerifier` traits decouple `Password` from a specific key
+//! derivation function. They are implemented for `Argon2` and, with the `bcrypt` feature, for
+//! `Bcrypt`, and can be implemented for custom or experimental backends. With the
+//! `prehash-long` feature, backends receive the pre-hashed input for long passwords.
+//!
+//! The `RemoteVerifier` trait delegates verification to another service holding the hashes,
+//! such as an authentication microservice. No network implementation is provided.
+
+#[cfg(feature = "os-rng")]
+use crate::{hash_kdf_input, HashError};
+use crate::{prehash, Hashed, Password, Plain};
+#[cfg(feature = "argon2")]
+use crate::{verify_phc, VerifyError};
+#[cfg(feature = "argon2")]
+use argon2::Argon2;
+use std::future::Future;
+
+/// Backend able to hash plain text passwords.
+pub trait PasswordHasher {
+    /// Error returned when hashing fails.
+    type Error;
+
+    /// Hashes the plain text password.
+    ///
+    /// - `plain`: The plain text password bytes.
+    ///
+    /// The backend is responsible for generating a salt and encoding it in the returned string.
+    ///
+    /// Returns the encoded hash, or `Self::Error` if hashing fails.
+    fn hash(&self, plain: &[u8]) -> Result<String, Self::Error>;
+}
+
+/// Backend able to verify plain text passwords against hashes it produced.
+pub trait PasswordVerifier {
+    /// Error returned when verification cannot be performed.
+    type Error;
+
+    /// Checks whether the plain text password matches the encoded hash.
+    ///
+    /// - `hash`: The encoded hash.
+    /// - `plain`: The plain text password bytes.
+    ///
+    /// Returns `Ok(true)` if the password matches, `Ok(false)` if it does not, and
+    /// `Self::Error` if the hash is malformed or the backend fails.
+    fn verify(&self, hash: &str, plain: &[u8]) -> Result<bool, Self::Error>;
+}
+
+/// Service verifying plain text passwords against hashes it stores, identified by an id.
+pub trait RemoteVerifier {
+    /// Error returned when verification cannot be performed, such as a network failure.
+    type Error;
+
+    /// Checks whether the plain text password matches the hash stored under the id.
+    ///
+    /// - `hash_id`: The identifier of the stored hash, such as a user id.
+    /// - `plain`: The plain text password, which is sent to the service as is.
+    ///
+    /// Returns `Ok(true)` if the password matches, `Ok(false)` if it does not, and
+    /// `Self::Error` if the service cannot perform the verification.
+    fn verify(
+        &self,
+        hash_id: &str,
+        plain: &Password<Plain>,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+#[cfg(feature = "os-rng")]
+impl PasswordHasher for Argon2<'_> {
+    type Error = HashError;
+
+    /// Hashes the password with a salt from the operating system's random number generator.
+    fn hash(&self, plain: &[u8]) -> Result<String, Self::Error> {
+        hash_kdf_input(self, plain, crate::generate_salt().as_salt())
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl PasswordVerifier for Argon2<'_> {
+    type Error = VerifyError;
+
+    fn verify(&self, hash: &str, plain: &[u8]) -> Result<bool, Self::Error> {
+        verify_phc(hash, Some(self.clone()), plain)
+    }
+}
+
+/// Bcrypt backend with the provided cost.
+#[cfg(feature = "bcrypt")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bcrypt(pub u32);
+
+#[cfg(feature = "bcrypt")]
+impl Default for Bcrypt {
+    /// Uses `bcrypt::DEFAULT_COST`.
+    fn default() -> Self {
+        Bcrypt(bcrypt::DEFAULT_COST)
+    }
+}
+
+#[cfg(feature = "bcrypt")]
+impl PasswordHasher for Bcrypt {
+    type Error = bcrypt::BcryptError;
+
+    fn hash(&self, plain: &[u8]) -> Result<String, Self::Error> {
+        bcrypt::hash(plain, self.0)
+    }
+}
+
+#[cfg(feature = "bcrypt")]
+impl PasswordVerifier for Bcrypt {
+    type Error = bcrypt::BcryptError;
+
+    /// Also accepts hashes produced by PHP's `password_hash`, see `compat::normalize_php`. With
+    /// the `prehash-long` feature enabled, those of passwords longer than `PREHASH_THRESHOLD`
+    /// only match through `compat::verify_php`, which skips pre-hashing as PHP does.
+    fn verify(&self, hash: &str, plain: &[u8]) -> Result<bool, Self::Error> {
+        bcrypt::verify(plain, &crate::compat::normalize_php(hash))
+    }
+}
+
+impl Password<Plain> {
+    /// Hashes the password using the provided backend.
+    ///
+    /// - `backend`: Any `PasswordHasher`, such as `Argon2` or a custom backend.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If hashing fails, the error of the backend is returned.
+    pub fn hash_with<B: PasswordHasher + ?Sized>(
+        &self,
+        backend: &B,
+    ) -> Result<Password<Hashed>, B::Error> {
+        backend
+            .hash(&prehash::kdf_input(self.as_bytes()))
+            .map(Password::new_unchecked)
+    }
+
+    /// Verifies the password against a hash stored by a remote service.
+    ///
+    /// - `verifier`: The `RemoteVerifier` holding the hash.
+    /// - `hash_id`: The identifier of the stored hash, such as a user id.
+    ///
+    /// Returns `Ok(true)` if the password matches, `Ok(false)` if it does not, and the error of
+    /// the verifier if verification cannot be performed.
+    pub async fn verify_remote<V: RemoteVerifier + ?Sized>(
+        &self,
+        verifier: &V,
+        hash_id: &str,
+    ) -> Result<bool, V::Error> {
+        verifier.verify(hash_id, self).await
+    }
+}
+
+impl Password<Hashed> {
+    /// Checks whether the hashed password matches the provided plain text password using the
+    /// provided backend.
+    ///
+    /// - `backend`: Any `PasswordVerifier`, such as `Argon2` or a custom backend.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Returns `Ok(true)` if the passwords match, `Ok(false)` if they do not, and the error of
+    /// the backend if verification cannot be performed.
+    pub fn verify_with<B: PasswordVerifier + ?Sized>(
+        &self,
+        backend: &B,
+        plain: &Password<Plain>,
+    ) -> Result<bool, B::Error> {
+        backend.verify(self.raw(), &prehash::kdf_input(plain.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PasswordHasher, PasswordVerifier, RemoteVerifier};
+    use crate::{Password, Plain};
+    use std::{collections::HashMap, convert::Infallible, future::Future};
+
+    /// Reversible backend for testing only, prefixing the password with `rev$` and reversing it.
+    struct Reverse;
+
+    impl PasswordHasher for Reverse {
+        type Error = Infallible;
+
+        fn hash(&self, plain: &[u8]) -> Result<String, Self::Error> {
+            Ok(format!(
+                "rev${}",
+                String::from_utf8_lossy(plain)
+                    .chars()
+                    .rev()
+                    .collect::<String>()
+            ))
+        }
+    }
+
+    impl PasswordVerifier for Reverse {
+        type Error = Infallible;
+
+        fn verify(&self, hash: &str, plain: &[u8]) -> Result<bool, Self::Error> {
+            Ok(self.hash(plain)? == hash)
+        }
+    }
+
+    /// In-memory stand-in for a remote service storing plain text passwords by id.
+    struct MockService(HashMap<&'static str, &'static str>);
+
+    impl RemoteVerifier for MockService {
+        type Error = &'static str;
+
+        fn verify(
+            &self,
+            hash_id: &str,
+            plain: &Password<Plain>,
+        ) -> impl Future<Output = Result<bool, Self::Error>> + Send {
+            let result = match self.0.get(hash_id) {
+                Some(stored) => Ok(*stored == plain.raw()),
+                None => Err("unknown hash id"),
+            };
+            async move { result }
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_verification() {
+        let service = MockService(HashMap::from([("admin", "Password")]));
+
+        assert_eq!(
+            Password::<Plain>::new("Password")
+                .verify_remote(&service, "admin")
+                .await,
+            Ok(true)
+        );
+        assert_eq!(
+            Password::<Plain>::new("Wrong")
+                .verify_remote(&service, "admin")
+                .await,
+            Ok(false)
+        );
+        assert_eq!(
+            Password::<Plain>::new("Password")
+                .verify_remote(&service, "guest")
+                .await,
+            Err("unknown hash id")
+        );
+    }
+
+    #[test]
+    fn custom_backend_roundtrip() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let hashed_password = plain_password
+            .hash_with(&Reverse)
+            .expect("Hashing should not fail.");
+
+        assert_eq!(hashed_password.raw(), "rev$drowssaP");
+        assert_eq!(
+            hashed_password.verify_with(&Reverse, &plain_password),
+            Ok(true)
+        );
+        assert_eq!(
+            hashed_password.verify_with(&Reverse, &Password::new("Wrong")),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "os-rng")]
+    fn argon2_backend_roundtrip() {
+        let argon2 = crate::Argon2Profile::Testing.argon2();
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let hashed_password = plain_password
+            .hash_with(&argon2)
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(
+            hashed_password.verify_with(&argon2, &plain_password),
+            Ok(true)
+        );
+        assert_eq!(
+            hashed_password.verify_with(&argon2, &Password::new("Wrong")),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bcrypt")]
+    fn bcrypt_backend_roundtrip() {
+        let backend = super::Bcrypt(4);
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let hashed_password = plain_password
+            .hash_with(&backend)
+            .expect("Bcrypt encoding should not fail.");
+
+        assert!(matches!(
+            hashed_password.verify_with(&backend, &plain_password),
+            Ok(true)
+        ));
+        assert!(matches!(
+            hashed_password.verify_with(&backend, &Password::new("Wrong")),
+            Ok(false)
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+//! Fluent construction of hashed passwords from untrusted plain text input.
+
+use crate::{BuildError, Hashed, Password, PasswordPolicy, Plain};
+use argon2::{password_hash::Salt, Algorithm, Argon2, Params, Version};
+
+/// Builder sanitizing, validating and hashing a plain text password in one chain.
+///
+/// Steps are applied in a fixed order when `build` is called, regardless of the order they
+/// were configured in: control characters are removed first, then the password is validated
+/// against the policy, then hashed with Argon2id. The first failing step short-circuits the
+/// remaining ones.
+///
+/// ```rust
+/// use tag_password::{PasswordBuilder, PasswordPolicy};
+/// use argon2::{password_hash::SaltString, Params};
+///
+/// let policy = PasswordPolicy::default();
+/// let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+/// let hashed_password = PasswordBuilder::new("correct horse")
+///     .policy(&policy)
+///     .sanitize_control_chars()
+///     .argon2(Params::new(8, 1, 1, None).expect("Argon2 parameters should be valid."))
+///     .salt(&salt)
+///     .build()
+///     .expect("Building should not fail.");
+/// ```
+#[derive(Debug)]
+pub struct PasswordBuilder<'a> {
+    password: Password<Plain>,
+    policy: Option<&'a PasswordPolicy>,
+    sanitize: bool,
+    params: Option<Params>,
+    salt: Option<Salt<'a>>,
+}
+
+impl<'a> PasswordBuilder<'a> {
+    /// Creates a new `PasswordBuilder` from the provided plain text password.
+    pub fn new(plain: impl Into<String>) -> Self {
+        PasswordBuilder {
+            password: Password::new(plain),
+            policy: None,
+            sanitize: false,
+            params: None,
+            salt: None,
+        }
+    }
+
+    /// Validates the password against the provided policy before hashing.
+    pub fn policy(mut self, policy: &'a PasswordPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Removes control characters from the password before validating and hashing it, see
+    /// `Password::sanitize_control_chars`.
+    pub fn sanitize_control_chars(mut self) -> Self {
+        self.sanitize = true;
+        self
+    }
+
+    /// Hashes the password with Argon2id and the provided parameters, the defaults being
+    /// used otherwise.
+    pub fn argon2(mut self, params: Params) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Sets the salt used for hashing.
+    ///
+    /// With the `os-rng` feature enabled, a random salt is generated when none is set.
+    pub fn salt(mut self, salt: impl Into<Salt<'a>>) -> Self {
+        self.salt = Some(salt.into());
+        self
+    }
+
+    /// Sanitizes, validates and hashes the password.
+    ///
+    /// Produces a result containing the hashed password if successful. If the password
+    /// violates the policy, `BuildError::Policy` is returned without hashing it. If no salt
+    /// was set and none can be generated, `BuildError::MissingSalt` is returned, and if
+    /// hashing fails, `BuildError::Hash` is returned.
+    pub fn build(self) -> Result<Password<Hashed>, BuildError> {
+        let password = match self.sanitize {
+            true => self.password.sanitize_control_chars().0,
+            false => self.password,
+        };
+        if let Some(policy) = self.policy {
+            password.validate(policy).map_err(BuildError::Policy)?;
+        }
+
+        let argon2 = Argon2::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            self.params.unwrap_or_default(),
+        );
+        match self.salt {
+            Some(salt) => Ok(password.hash_shared(&argon2, salt)?),
+            #[cfg(feature = "os-rng")]
+            None => Ok(password.hash_shared(&argon2, &crate::generate_salt())?),
+            #[cfg(not(feature = "os-rng"))]
+            None => Err(BuildError::MissingSalt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PasswordBuilder;
+    use crate::{
+        Argon2Profile, BuildError, CharacterClass, HashError, LengthUnit, PasswordPolicy,
+        PolicyViolation, TEST_HASH,
+    };
+    use argon2::password_hash::SaltString;
+
+    fn salt() -> SaltString {
+        SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.")
+    }
+
+    #[test]
+    fn builds_sanitized_hash() {
+        let policy = PasswordPolicy::default().require(CharacterClass::Uppercase);
+        let salt = salt();
+
+        let hashed_password = PasswordBuilder::new("Pass\0word\r\n")
+            .policy(&policy)
+            .sanitize_control_chars()
+            .argon2(Argon2Profile::Testing.params())
+            .salt(&salt)
+            .build()
+            .expect("Building should not fail.");
+
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+    }
+
+    #[test]
+    fn policy_failure_short_circuits() {
+        let policy = PasswordPolicy::default().require(CharacterClass::Digit);
+
+        assert_eq!(
+            PasswordBuilder::new("")
+                .policy(&policy)
+                .argon2(Argon2Profile::Testing.params())
+                .build(),
+            Err(BuildError::Policy(vec![
+                PolicyViolation::TooShort {
+                    min: 8,
+                    unit: LengthUnit::Chars,
+                },
+                PolicyViolation::MissingClass(CharacterClass::Digit),
+            ]))
+        );
+    }
+
+    #[test]
+    fn hashing_failure_is_reported() {
+        let salt = salt();
+
+        assert_eq!(
+            PasswordBuilder::new("")
+                .argon2(Argon2Profile::Testing.params())
+                .salt(&salt)
+                .build(),
+            Err(BuildError::Hash(HashError::EmptyInput))
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Short-lived cache of password verification results.
+//!
+//! # Security
+//!
+//! Caching verification results deliberately bypasses the cost of Argon2 for repeated
+//! attempts, which is the property that slows down brute force attacks. While an entry is
+//! cached, the same plain text password is accepted without rehashing, and a process memory
+//! dump exposes which (hash, password) pairs were verified recently, even though only keyed
+//! digests are stored. Keep the TTL short, bound the size, and only use the cache where the
+//! same credentials are verified in bursts, such as HTTP basic authentication.
+
+use crate::{keyed::hmac_sha256, Hashed, Password, Plain, VerifyError};
+use argon2::{password_hash::rand_core::RngCore, Argon2};
+use rand_core::OsRng;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Cache of verification results keyed on a salted digest of the hash and plain text password.
+///
+/// Each cache draws a random key from the operating system on creation, so digests cannot be
+/// precomputed or compared across caches.
+pub struct VerifyCache {
+    key: [u8; 32],
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<[u8; 32], (bool, Instant)>>,
+}
+
+impl VerifyCache {
+    /// Creates a new, empty cache.
+    ///
+    /// - `ttl`: How long a verification result is reused.
+    /// - `capacity`: The maximum number of cached results.
+    ///
+    /// Returns a `VerifyCache` with a random key.
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        VerifyCache {
+            key,
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Checks whether the hashed password matches the plain text password, reusing a recent
+    /// result when available.
+    ///
+    /// - `hashed`: The stored hashed password.
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Behaves like `Password::try_verify`. Errors are never cached.
+    ///
+    /// Returns `Ok(true)` if the passwords match, `Ok(false)` if they do not, and a
+    /// `VerifyError` if the stored hash cannot be parsed or the backend fails.
+    pub fn verify(
+        &self,
+        hashed: &Password<Hashed>,
+        argon2: Option<Argon2>,
+        plain: &Password<Plain>,
+    ) -> Result<bool, VerifyError> {
+        let key = self.digest(hashed, plain);
+        if let Some(result) = self.lookup(&key) {
+            return Ok(result);
+        }
+
+        let result = hashed.try_verify(argon2, plain.clone())?;
+        self.insert(key, result);
+        Ok(result)
+    }
+
+    /// Returns the number of cached results, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    fn digest(&self, hashed: &Password<Hashed>, plain: &Password<Plain>) -> [u8; 32] {
+        let hash = hashed.as_bytes();
+        let mut message = Vec::with_capacity(8 + hash.len() + plain.as_bytes().len());
+        message.extend_from_slice(&(hash.len() as u64).to_be_bytes());
+        message.extend_from_slice(hash);
+        message.extend_from_slice(plain.as_bytes());
+        hmac_sha256(&self.key, &message)
+    }
+
+    fn lookup(&self, key: &[u8; 32]) -> Option<bool> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some(&(result, inserted)) if inserted.elapsed() < self.ttl => Some(result),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: [u8; 32], result: bool) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries();
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            entries.retain(|_, (_, inserted)| inserted.elapsed() < self.ttl);
+        }
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (_, inserted))| *inserted)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (result, Instant::now()));
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<[u8; 32], (bool, Instant)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for VerifyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerifyCache")
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VerifyCache;
+    use crate::{Hashed, Password, Plain, TEST_HASH};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn cache_hit_and_miss() {
+        let cache = VerifyCache::new(Duration::from_secs(60), 8);
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let plain_password: Password<Plain> = Password::new("Password");
+        let wrong_password: Password<Plain> = Password::new("Wrong");
+        let key = cache.digest(&hashed_password, &plain_password);
+
+        assert_eq!(cache.lookup(&key), None);
+        assert_eq!(
+            cache.verify(&hashed_password, None, &plain_password),
+            Ok(true)
+        );
+        assert_eq!(cache.lookup(&key), Some(true));
+        assert_eq!(
+            cache.verify(&hashed_password, None, &plain_password),
+            Ok(true)
+        );
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.verify(&hashed_password, None, &wrong_password),
+            Ok(false)
+        );
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn cache_expiry_and_capacity() {
+        let cache = VerifyCache::new(Duration::from_millis(50), 1);
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let plain_password: Password<Plain> = Password::new("Password");
+        let key = cache.digest(&hashed_password, &plain_password);
+
+        assert_eq!(
+            cache.verify(&hashed_password, None, &plain_password),
+            Ok(true)
+        );
+        thread::sleep(Duration::from_millis(60));
+        assert_eq!(cache.lookup(&key), None);
+        assert!(cache.is_empty());
+
+        assert_eq!(
+            cache.verify(&hashed_password, None, &plain_password),
+            Ok(true)
+        );
+        assert_eq!(
+            cache.verify(&hashed_password, None, &Password::new("Wrong")),
+            Ok(false)
+        );
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.lookup(&key), None);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let cache = VerifyCache::new(Duration::from_secs(60), 8);
+
+        assert!(cache
+            .verify(
+                &Password::new_unchecked("not a hash"),
+                None,
+                &Password::new("Password")
+            )
+            .is_err());
+        assert!(cache.is_empty());
+    }
+}
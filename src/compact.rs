@@ -0,0 +1,83 @@
+//! Compact representation of PHC strings, used by non-human-readable serde formats and for
+//! hashes stored split across separate columns.
+
+use argon2::{
+    password_hash::{Ident, Output, ParamsString, Result, Salt, SaltString},
+    PasswordHash,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// PHC string split into its fields, with the salt and hash stored as raw bytes.
+///
+/// Storing the raw bytes avoids the Base64 overhead for the salt and hash in binary formats.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(crate) struct CompactHash {
+    /// Identifier of the algorithm.
+    pub(crate) algorithm: String,
+    /// Version of the algorithm, if any.
+    pub(crate) version: Option<u32>,
+    /// Parameters of the algorithm.
+    pub(crate) params: String,
+    /// Raw salt bytes, if any.
+    pub(crate) salt: Option<Vec<u8>>,
+    /// Raw hash bytes, if any.
+    pub(crate) hash: Option<Vec<u8>>,
+}
+
+impl CompactHash {
+    /// Splits a PHC string into its compact representation.
+    pub(crate) fn from_phc(value: &str) -> Result<Self> {
+        let hash = PasswordHash::new(value)?;
+        let salt = match hash.salt {
+            Some(salt) => {
+                let mut buf = [0u8; Salt::MAX_LENGTH];
+                Some(salt.decode_b64(&mut buf)?.to_vec())
+            }
+            None => None,
+        };
+
+        Ok(CompactHash {
+            algorithm: hash.algorithm.as_str().to_owned(),
+            version: hash.version,
+            params: hash.params.as_str().to_owned(),
+            salt,
+            hash: hash.hash.map(|output| output.as_bytes().to_vec()),
+        })
+    }
+
+    /// Reassembles the PHC string from its compact representation.
+    pub(crate) fn to_phc(&self) -> Result<String> {
+        let salt = self
+            .salt
+            .as_deref()
+            .map(SaltString::encode_b64)
+            .transpose()?;
+
+        Ok(PasswordHash {
+            algorithm: Ident::new(&self.algorithm)?,
+            version: self.version,
+            params: self.params.parse::<ParamsString>()?,
+            salt: salt.as_ref().map(SaltString::as_salt),
+            hash: self.hash.as_deref().map(Output::new).transpose()?,
+        }
+        .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactHash;
+    use crate::TEST_HASH;
+
+    #[test]
+    fn compact_roundtrip() {
+        let compact = CompactHash::from_phc(TEST_HASH).expect("Parsing should not fail.");
+
+        assert_eq!(compact.salt.as_deref(), Some(&b"tag_password_salt"[..]));
+        assert_eq!(compact.hash.as_ref().map(Vec::len), Some(32));
+        assert_eq!(compact.to_phc().as_deref(), Ok(TEST_HASH));
+        assert!(CompactHash::from_phc("Password").is_err());
+    }
+}
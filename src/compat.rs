@@ -0,0 +1,67 @@
+//! Compatibility helpers for hashes produced by other languages and frameworks.
+//!
+//! These helpers are available with the `bcrypt` feature and are meant to smooth migrations
+//! of existing password databases.
+
+use crate::{Password, Plain};
+
+/// Normalizes a PHP `password_hash` bcrypt string to the `$2b$` identifier.
+///
+/// PHP emits bcrypt hashes with the `$2y$` identifier, which is interoperable with `$2b$`.
+///
+/// - `stored`: The stored bcrypt hash.
+///
+/// Returns the hash with a `$2y$` prefix rewritten to `$2b$`, or the input unchanged otherwise.
+pub fn normalize_php(stored: &str) -> String {
+    match stored.strip_prefix("$2y$") {
+        Some(rest) => format!("$2b${rest}"),
+        None => stored.to_owned(),
+    }
+}
+
+/// Verifies a plain text password against a bcrypt hash produced by PHP's `password_hash`.
+///
+/// - `stored`: The stored bcrypt hash, typically starting with `$2y$`.
+/// - `plain`: A plain text password used for verification.
+///
+/// Unlike the `Bcrypt` backend, the password is passed to bcrypt as is, without the pre-hashing
+/// of the `prehash-long` feature: PHP never pre-hashes, so a pre-hashed password longer than
+/// `PREHASH_THRESHOLD` would not match its hash. Use `Password::<Hashed>::verify_with` and
+/// `Bcrypt` for hashes produced by this crate.
+///
+/// Returns `Ok(true)` if the password matches and `Ok(false)` otherwise. If the stored hash
+/// is malformed, a `bcrypt::BcryptResult` with an error is returned.
+pub fn verify_php(stored: &str, plain: impl Into<Password<Plain>>) -> bcrypt::BcryptResult<bool> {
+    bcrypt::verify(plain.into().as_bytes(), &normalize_php(stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_php, verify_php};
+    use crate::Password;
+
+    /// Hash of `"rasmuslerdorf"` from the PHP `password_verify` documentation.
+    const PHP_HASH: &str = "$2y$07$BCryptRequires22Chrcte/VlQH0piJtjXl.0t1XkA8pw9dMXTpOq";
+
+    #[test]
+    fn php_hash_normalization() {
+        assert_eq!(
+            normalize_php(PHP_HASH),
+            "$2b$07$BCryptRequires22Chrcte/VlQH0piJtjXl.0t1XkA8pw9dMXTpOq"
+        );
+        assert_eq!(normalize_php("$2b$07$abc"), "$2b$07$abc");
+    }
+
+    #[test]
+    fn php_hash_verification() {
+        assert_eq!(
+            verify_php(PHP_HASH, Password::new("rasmuslerdorf")).ok(),
+            Some(true)
+        );
+        assert_eq!(
+            verify_php(PHP_HASH, Password::new("Password")).ok(),
+            Some(false)
+        );
+        assert!(verify_php("$2y$07$invalid", Password::new("rasmuslerdorf")).is_err());
+    }
+}
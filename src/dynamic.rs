@@ -0,0 +1,177 @@
+//! Passwords whose state is only known at runtime.
+
+use crate::{constant_time_eq, is_hashed_format, HashError, Hashed, Password, Plain, VerifyError};
+use argon2::{password_hash::Salt, Argon2};
+
+/// Password that is either plain text or hashed, for values whose state is only known at
+/// runtime, such as rows of a store being migrated to hashed passwords.
+///
+/// Prefer `Password<Plain>` and `Password<Hashed>` wherever the state is known statically,
+/// and convert to them as early as possible.
+#[derive(Clone, Debug, Eq)]
+pub enum DynPassword {
+    /// A plain text password.
+    Plain(Password<Plain>),
+    /// A hashed password.
+    Hashed(Password<Hashed>),
+}
+
+impl DynPassword {
+    /// Creates a new `DynPassword` from a stored value, detecting whether it is hashed.
+    ///
+    /// - `value`: The stored value, either a PHC hash string or a plain text password.
+    ///
+    /// A plain text password that happens to be a well-formed PHC string is detected as
+    /// hashed, so only use this for stores that never held such passwords.
+    ///
+    /// Returns `DynPassword::Hashed` if the value is a well-formed PHC string, and
+    /// `DynPassword::Plain` otherwise.
+    pub fn detect(value: impl Into<String>) -> Self {
+        let value = value.into();
+        match is_hashed_format(&value) {
+            true => DynPassword::Hashed(Password::new_unchecked(value)),
+            false => DynPassword::Plain(Password::new(value)),
+        }
+    }
+
+    /// Returns `true` if the password is plain text.
+    pub fn is_plain(&self) -> bool {
+        matches!(self, DynPassword::Plain(_))
+    }
+
+    /// Returns `true` if the password is hashed.
+    pub fn is_hashed(&self) -> bool {
+        matches!(self, DynPassword::Hashed(_))
+    }
+
+    /// Hashes the password if it is plain text, returning it unchanged otherwise.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing the hashed password if successful. If the password is
+    /// plain text and hashing fails, a `HashError` is returned.
+    pub fn into_hashed<'a>(
+        self,
+        argon2: Option<Argon2>,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        match self {
+            DynPassword::Plain(plain) => plain.hash(argon2, salt),
+            DynPassword::Hashed(hashed) => Ok(hashed),
+        }
+    }
+
+    /// Checks whether the password matches the provided plain text password, if it is hashed.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Returns `None` if the password is plain text, and the outcome of `try_verify`
+    /// otherwise.
+    pub fn try_verify(
+        &self,
+        argon2: Option<Argon2>,
+        plain: impl Into<Password<Plain>>,
+    ) -> Option<Result<bool, VerifyError>> {
+        match self {
+            DynPassword::Plain(_) => None,
+            DynPassword::Hashed(hashed) => Some(hashed.try_verify(argon2, plain)),
+        }
+    }
+}
+
+impl PartialEq for DynPassword {
+    /// Compares the `DynPassword` values within the same state.
+    ///
+    /// Hashed passwords are compared by their PHC strings in constant time. A plain text
+    /// password never equals a hashed password, even if the hash was produced from it.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DynPassword::Plain(a), DynPassword::Plain(b)) => a == b,
+            (DynPassword::Hashed(a), DynPassword::Hashed(b)) => {
+                constant_time_eq(a.as_bytes(), b.as_bytes())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<Password<Plain>> for DynPassword {
+    /// Converts a `Password<Plain>` into a `DynPassword::Plain`.
+    fn from(value: Password<Plain>) -> Self {
+        DynPassword::Plain(value)
+    }
+}
+
+impl From<Password<Hashed>> for DynPassword {
+    /// Converts a `Password<Hashed>` into a `DynPassword::Hashed`.
+    fn from(value: Password<Hashed>) -> Self {
+        DynPassword::Hashed(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynPassword;
+    use crate::{Argon2Profile, Hashed, Password, Plain, TEST_HASH};
+    use argon2::password_hash::SaltString;
+
+    #[test]
+    fn detects_state() {
+        let plain = DynPassword::detect("Password");
+        let hashed = DynPassword::detect(TEST_HASH);
+
+        assert!(plain.is_plain() && !plain.is_hashed());
+        assert!(hashed.is_hashed() && !hashed.is_plain());
+        assert_eq!(DynPassword::from(Password::<Plain>::new("Password")), plain);
+        assert_eq!(
+            DynPassword::from(Password::<Hashed>::new_unchecked(TEST_HASH)),
+            hashed
+        );
+    }
+
+    #[test]
+    fn compares_within_same_state() {
+        let plain = DynPassword::detect("Password");
+        let hashed = DynPassword::detect(TEST_HASH);
+
+        assert_eq!(plain, DynPassword::detect("Password"));
+        assert_ne!(plain, DynPassword::detect("Wrong"));
+        assert_eq!(hashed, DynPassword::detect(TEST_HASH));
+        assert_ne!(
+            hashed,
+            DynPassword::detect("$argon2id$v=19$m=8,t=1,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA")
+        );
+        assert_ne!(plain, hashed);
+        assert_ne!(DynPassword::Plain(Password::new(TEST_HASH)), hashed);
+    }
+
+    #[test]
+    fn dispatches_on_state() {
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let plain = DynPassword::detect("Password");
+        let hashed = DynPassword::detect(TEST_HASH);
+
+        assert_eq!(plain.try_verify(None, "Password".to_owned()), None);
+        assert_eq!(
+            hashed.try_verify(None, "Password".to_owned()),
+            Some(Ok(true))
+        );
+        assert_eq!(hashed.try_verify(None, "Wrong".to_owned()), Some(Ok(false)));
+        assert_eq!(
+            plain
+                .into_hashed(Some(Argon2Profile::Testing.argon2()), &salt)
+                .expect("Argon2 encoding should not fail.")
+                .raw(),
+            TEST_HASH
+        );
+        assert_eq!(
+            hashed
+                .into_hashed(None, &salt)
+                .expect("Hashed passwords should be returned unchanged.")
+                .raw(),
+            TEST_HASH
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Error types returned by password operations.
+
+use std::fmt::{Display, Formatter};
+
+/// Error returned when a password value is not valid UTF-8.
+///
+/// The rejected value is not kept, so the error can be logged safely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidUtf8Error;
+
+impl Display for InvalidUtf8Error {
+    /// Formats the `InvalidUtf8Error` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "password is not valid UTF-8")
+    }
+}
+
+impl std::error::Error for InvalidUtf8Error {}
+
+/// Error returned when a password expected on a single line contains a line break.
+///
+/// The rejected value is not kept, so the error can be logged safely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultilineError;
+
+impl Display for MultilineError {
+    /// Formats the `MultilineError` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "password contains a line break")
+    }
+}
+
+impl std::error::Error for MultilineError {}
+
+/// Error returned when a hashed password could not be verified.
+///
+/// Functions reporting a match as a boolean, such as `try_verify`, only use this type to
+/// describe problems with the stored hash or the backend, while one-shot functions such as
+/// `verify_str` also report a wrong password as `VerifyError::Mismatch`.
+///
+/// Neither the password nor the stored hash is included in the error, so its `Display` and
+/// `Debug` output can be logged safely. With the `std` feature enabled, the underlying
+/// `password_hash::Error` is available through `source()`.
+#[cfg(feature = "argon2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The password does not match the stored hash.
+    Mismatch,
+    /// The stored hash could not be parsed as a PHC string.
+    InvalidHash(argon2::password_hash::Error),
+    /// The hashing backend failed while verifying the password.
+    Backend(argon2::password_hash::Error),
+    /// The password is longer than the maximum length, in bytes, accepted for verification.
+    TooLong {
+        /// The maximum length, in bytes.
+        max: usize,
+    },
+    /// The memory required by the stored Argon2 parameters could not be allocated.
+    AllocationFailed {
+        /// The stored memory cost, in KiB.
+        memory_kib: u32,
+    },
+}
+
+#[cfg(feature = "argon2")]
+impl Display for VerifyError {
+    /// Formats the `VerifyError` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::Mismatch => write!(f, "password does not match"),
+            VerifyError::InvalidHash(_) => write!(f, "invalid password hash"),
+            VerifyError::Backend(_) => write!(f, "password verification failed"),
+            VerifyError::TooLong { max } => write!(f, "password is longer than {max} bytes"),
+            VerifyError::AllocationFailed { memory_kib } => {
+                write!(
+                    f,
+                    "failed to allocate {memory_kib} KiB of memory for verification"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl std::error::Error for VerifyError {
+    /// Returns the underlying `password_hash::Error`, if any.
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VerifyError::Mismatch
+            | VerifyError::TooLong { .. }
+            | VerifyError::AllocationFailed { .. } => None,
+            VerifyError::InvalidHash(error) | VerifyError::Backend(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when a plain text password could not be hashed.
+///
+/// The password is not included in the error, so its `Display` and `Debug` output can be
+/// logged safely. With the `std` feature enabled, the underlying `password_hash::Error` of the
+/// `InvalidParams` and `Backend` variants is available through `source()`.
+#[cfg(feature = "argon2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HashError {
+    /// The password is empty.
+    EmptyInput,
+    /// The password is longer than the maximum length, in bytes, accepted for hashing.
+    TooLong {
+        /// The maximum length, in bytes.
+        max: usize,
+    },
+    /// The Argon2 parameters, salt or output length are invalid.
+    InvalidParams(argon2::password_hash::Error),
+    /// The hashing backend failed while hashing the password.
+    Backend(argon2::password_hash::Error),
+    /// The memory required by the Argon2 parameters could not be allocated.
+    AllocationFailed {
+        /// The requested memory cost, in KiB.
+        memory_kib: u32,
+    },
+}
+
+#[cfg(feature = "argon2")]
+impl From<argon2::password_hash::Error> for HashError {
+    /// Classifies a `password_hash::Error` as `InvalidParams` or `Backend`.
+    fn from(error: argon2::password_hash::Error) -> Self {
+        use argon2::password_hash::Error;
+
+        match error {
+            Error::OutputSize { .. }
+            | Error::ParamNameDuplicated
+            | Error::ParamNameInvalid
+            | Error::ParamValueInvalid(_)
+            | Error::ParamsMaxExceeded
+            | Error::SaltInvalid(_) => HashError::InvalidParams(error),
+            _ => HashError::Backend(error),
+        }
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl Display for HashError {
+    /// Formats the `HashError` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashError::EmptyInput => write!(f, "password is empty"),
+            HashError::TooLong { max } => write!(f, "password is longer than {max} bytes"),
+            HashError::InvalidParams(_) => write!(f, "invalid hashing parameters"),
+            HashError::Backend(_) => write!(f, "password hashing failed"),
+            HashError::AllocationFailed { memory_kib } => {
+                write!(
+                    f,
+                    "failed to allocate {memory_kib} KiB of memory for hashing"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl std::error::Error for HashError {
+    /// Returns the underlying `password_hash::Error`, if any.
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HashError::EmptyInput
+            | HashError::TooLong { .. }
+            | HashError::AllocationFailed { .. } => None,
+            HashError::InvalidParams(error) | HashError::Backend(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned when a `PasswordBuilder` could not produce a hashed password.
+///
+/// The password is not included in the error. With the `std` feature enabled, the underlying
+/// `HashError` of the `Hash` variant is available through `source()`.
+#[cfg(feature = "argon2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The password violates the rules of the policy.
+    Policy(Vec<crate::PolicyViolation>),
+    /// No salt was set and none could be generated.
+    MissingSalt,
+    /// The password could not be hashed.
+    Hash(HashError),
+}
+
+#[cfg(feature = "argon2")]
+impl From<HashError> for BuildError {
+    /// Wraps a `HashError` as `BuildError::Hash`.
+    fn from(error: HashError) -> Self {
+        BuildError::Hash(error)
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl Display for BuildError {
+    /// Formats the `BuildError` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::Policy(violations) => {
+                write!(f, "password violates {} policy rules", violations.len())
+            }
+            BuildError::MissingSalt => write!(f, "no salt was provided"),
+            BuildError::Hash(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl std::error::Error for BuildError {
+    /// Returns the underlying `HashError`, if any.
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Policy(_) | BuildError::MissingSalt => None,
+            BuildError::Hash(error) => Some(error),
+        }
+    }
+}
+
+/// Error returned by `verify_tracked` when a password is not accepted.
+///
+/// The password is not included in the error. With the `std` feature enabled, the underlying
+/// `VerifyError` of the `Verify` variant is available through `source()`.
+#[cfg(feature = "argon2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LockedOrMismatch {
+    /// No attempts are left, so the password was not verified.
+    Locked,
+    /// The password does not match the stored hash.
+    Mismatch {
+        /// The number of failed attempts still allowed before the account is locked.
+        remaining: u32,
+    },
+    /// The stored hash could not be verified.
+    Verify(VerifyError),
+}
+
+#[cfg(feature = "argon2")]
+impl Display for LockedOrMismatch {
+    /// Formats the `LockedOrMismatch` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockedOrMismatch::Locked => write!(f, "too many failed attempts"),
+            LockedOrMismatch::Mismatch { remaining } => {
+                write!(f, "password does not match, {remaining} attempts left")
+            }
+            LockedOrMismatch::Verify(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(feature = "argon2")]
+impl std::error::Error for LockedOrMismatch {
+    /// Returns the underlying `VerifyError`, if any.
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LockedOrMismatch::Locked | LockedOrMismatch::Mismatch { .. } => None,
+            LockedOrMismatch::Verify(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "argon2", feature = "std"))]
+mod tests {
+    use super::{BuildError, HashError, LockedOrMismatch, VerifyError};
+    use crate::{
+        verify_str, Argon2Profile, CharacterClass, Hashed, InMemoryTracker, Password,
+        PasswordBuilder, PasswordPolicy, Plain, TEST_HASH,
+    };
+    use argon2::password_hash::{errors::InvalidValue, Error, SaltString};
+    use proptest::prelude::*;
+    use std::error::Error as _;
+
+    /// Collects the `Display` and `Debug` output of the error and all of its sources.
+    fn formatted(error: &(dyn std::error::Error + 'static)) -> String {
+        let mut output = String::new();
+        let mut current = Some(error);
+        while let Some(error) = current {
+            output += &format!("{error} {error:?}\n");
+            current = error.source();
+        }
+        output
+    }
+
+    #[test]
+    fn verify_error_source_chaining() {
+        let backend = VerifyError::Backend(Error::Crypto);
+        let invalid_hash = VerifyError::InvalidHash(Error::PhcStringField);
+
+        assert_eq!(
+            backend
+                .source()
+                .and_then(|source| source.downcast_ref::<Error>()),
+            Some(&Error::Crypto)
+        );
+        assert_eq!(
+            invalid_hash
+                .source()
+                .and_then(|source| source.downcast_ref::<Error>()),
+            Some(&Error::PhcStringField)
+        );
+        assert!(VerifyError::Mismatch.source().is_none());
+        assert_eq!(backend.to_string(), "password verification failed");
+    }
+
+    #[test]
+    fn hash_error_classification() {
+        let invalid_params = HashError::from(Error::ParamValueInvalid(InvalidValue::TooShort));
+        let backend = HashError::from(Error::Crypto);
+
+        assert_eq!(
+            invalid_params,
+            HashError::InvalidParams(Error::ParamValueInvalid(InvalidValue::TooShort))
+        );
+        assert_eq!(backend, HashError::Backend(Error::Crypto));
+        assert_eq!(
+            backend
+                .source()
+                .and_then(|source| source.downcast_ref::<Error>()),
+            Some(&Error::Crypto)
+        );
+        assert!(HashError::EmptyInput.source().is_none());
+        assert_eq!(
+            HashError::TooLong { max: 8 }.to_string(),
+            "password is longer than 8 bytes"
+        );
+    }
+
+    #[test]
+    fn errors_never_contain_secrets() {
+        let secret = "Tr0ub4dor&3";
+        let corrupt_hash = format!("$argon2id$v=19$m=8,t=1,p=1${secret}");
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let policy = PasswordPolicy::default()
+            .min_length(64)
+            .require(CharacterClass::Symbol);
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let mut tracker = InMemoryTracker::new(1);
+
+        let errors: Vec<Box<dyn std::error::Error>> = vec![
+            Box::new(verify_str(TEST_HASH, secret, None).expect_err("Verification should fail.")),
+            Box::new(
+                verify_str(&corrupt_hash, secret, None).expect_err("Verification should fail."),
+            ),
+            Box::new(VerifyError::Backend(Error::Crypto)),
+            Box::new(
+                Password::<Plain>::new("")
+                    .hash(Some(Argon2Profile::Testing.argon2()), &salt)
+                    .expect_err("Hashing should fail."),
+            ),
+            Box::new(HashError::TooLong { max: 8 }),
+            Box::new(HashError::InvalidParams(Error::ParamsMaxExceeded)),
+            Box::new(HashError::Backend(Error::Crypto)),
+            Box::new(HashError::AllocationFailed { memory_kib: 8 }),
+            Box::new(
+                PasswordBuilder::new(secret)
+                    .policy(&policy)
+                    .salt(&salt)
+                    .build()
+                    .expect_err("Building should fail."),
+            ),
+            Box::new(BuildError::MissingSalt),
+            Box::new(BuildError::Hash(HashError::EmptyInput)),
+            Box::new(
+                hashed_password
+                    .verify_tracked(None, secret.to_owned(), &mut tracker)
+                    .expect_err("Verification should fail."),
+            ),
+            Box::new(
+                hashed_password
+                    .verify_tracked(None, secret.to_owned(), &mut tracker)
+                    .expect_err("Verification should fail."),
+            ),
+            Box::new(
+                Password::<Hashed>::new_unchecked(corrupt_hash.clone())
+                    .verify_tracked(None, secret.to_owned(), &mut InMemoryTracker::new(1))
+                    .expect_err("Verification should fail."),
+            ),
+            Box::new(VerifyError::TooLong { max: 8 }),
+            Box::new(VerifyError::AllocationFailed { memory_kib: 8 }),
+        ];
+
+        for error in &errors {
+            let output = formatted(error.as_ref());
+            assert!(!output.contains(secret), "{output}");
+            assert!(!output.contains(TEST_HASH), "{output}");
+            assert!(!output.contains(&corrupt_hash), "{output}");
+        }
+        assert!(matches!(
+            errors[12].downcast_ref::<LockedOrMismatch>(),
+            Some(LockedOrMismatch::Locked)
+        ));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn verify_errors_never_contain_plaintext(secret in "[A-Za-z0-9]{16,32}") {
+            let mismatch = verify_str(TEST_HASH, &secret, None)
+                .expect_err("Verification should fail.");
+            let invalid_hash = verify_str(&format!("$argon2id${secret}$"), &secret, None)
+                .expect_err("Verification should fail.");
+
+            prop_assert!(!formatted(&mismatch).contains(&secret));
+            prop_assert!(!formatted(&invalid_hash).contains(&secret));
+        }
+    }
+}
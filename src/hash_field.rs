@@ -0,0 +1,116 @@
+//! Serde helpers hashing submitted plain text passwords in request DTOs.
+//!
+//! Use this module with `#[serde(with = "tag_password::hash_field")]` on a `Password<Hashed>`
+//! field of a request DTO to accept a submitted plain text password and hash it while the
+//! request is deserialized, so handlers never see the plain text value. Serializing the field
+//! writes nothing but a none value; combine it with `skip_serializing` to omit it entirely.
+//!
+//! Hashing happens during deserialization, on the thread performing it. With the default
+//! Argon2 configuration this takes tens of milliseconds per field, so deserializing untrusted
+//! input with many such fields can be used for denial of service, and payloads that fail
+//! validation after deserialization still pay the hashing cost. Run deserialization off async
+//! executors where needed, and use `with_argon2` to choose the configuration.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use tag_password::{Hashed, Password};
+//!
+//! #[derive(Deserialize)]
+//! struct SignUp {
+//!     name: String,
+//!     #[serde(with = "tag_password::hash_field")]
+//!     password: Password<Hashed>,
+//! }
+//! ```
+
+use crate::{generate_salt, Hashed, Password, Plain};
+use argon2::Argon2;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+use std::cell::RefCell;
+
+thread_local! {
+    static ARGON2: RefCell<Option<Argon2<'static>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous Argon2 configuration when dropped.
+struct Argon2Guard(Option<Argon2<'static>>);
+
+impl Drop for Argon2Guard {
+    fn drop(&mut self) {
+        ARGON2.with(|argon2| *argon2.borrow_mut() = self.0.take());
+    }
+}
+
+/// Runs the provided closure with fields hashed using the provided Argon2 configuration on the
+/// current thread.
+///
+/// - `argon2`: The `Argon2` configuration used to hash fields.
+/// - `f`: The closure performing the deserialization.
+///
+/// Returns the value produced by the closure.
+pub fn with_argon2<R>(argon2: Argon2<'static>, f: impl FnOnce() -> R) -> R {
+    let _guard = Argon2Guard(ARGON2.with(|current| current.replace(Some(argon2))));
+    f()
+}
+
+/// Deserializes a plain text value and hashes it into a `Password<Hashed>`.
+///
+/// The value is hashed with a new random salt, using the configuration set by `with_argon2`
+/// or the default Argon2 configuration otherwise.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Password<Hashed>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let plain = Password::<Plain>::deserialize(deserializer)?;
+    let argon2 = ARGON2.with(|argon2| argon2.borrow().clone());
+    plain
+        .hash(argon2, &generate_salt())
+        .map_err(D::Error::custom)
+}
+
+/// Serializes the hashed password as a none value, keeping the hash out of the output.
+pub fn serialize<S>(_: &Password<Hashed>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::with_argon2;
+    use crate::{is_hashed_format, Argon2Profile, Hashed, Password};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    struct SignUp {
+        name: String,
+        #[serde(with = "super")]
+        password: Password<Hashed>,
+    }
+
+    #[test]
+    fn deserialize_hashes_submitted_password() {
+        let sign_up: SignUp = with_argon2(Argon2Profile::Testing.argon2(), || {
+            serde_json::from_str(r#"{"name":"admin","password":"Password"}"#)
+        })
+        .expect("Deserialization should not fail.");
+
+        assert_eq!(sign_up.name, "admin");
+        assert!(is_hashed_format(&sign_up.password));
+        assert!(sign_up.password.starts_with("$argon2id$v=19$m=8,t=1,p=1$"));
+        assert!(sign_up.password.matches(&Password::new("Password")));
+        assert_eq!(
+            serde_json::to_string(&sign_up).expect("Serialization should not fail."),
+            r#"{"name":"admin","password":null}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_rejects_empty_password() {
+        assert!(with_argon2(Argon2Profile::Testing.argon2(), || {
+            serde_json::from_str::<SignUp>(r#"{"name":"admin","password":""}"#)
+        })
+        .is_err());
+    }
+}
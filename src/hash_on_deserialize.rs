@@ -0,0 +1,49 @@
+//! Serde helpers hashing plain text fields while they are deserialized.
+//!
+//! Use this module with `#[serde(with = "tag_password::hash_on_deserialize")]` on a
+//! `Password<Hashed>` field to accept a plain text value, for example an initial admin
+//! password in a configuration file, and hash it before it ever reaches the rest of the
+//! application. Serializing the field writes the hash.
+//!
+//! Values are deserialized and hashed exactly as with `hash_field`, which only differs in
+//! writing nothing when serializing. Use `with_argon2` to choose the Argon2 configuration.
+//!
+//! Keeping plain text passwords in configuration means anyone able to read the configuration
+//! can read the password. Prefer provisioning an already hashed value where possible, and
+//! rotate any password that was bootstrapped this way.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use tag_password::{Hashed, Password};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     #[serde(with = "tag_password::hash_on_deserialize")]
+//!     admin_password: Password<Hashed>,
+//! }
+//! ```
+
+use crate::{hash_field, Hashed, Password};
+use serde::{Deserializer, Serializer};
+
+pub use crate::hash_field::with_argon2;
+
+/// Deserializes a plain text value and hashes it into a `Password<Hashed>`.
+///
+/// This delegates to `hash_field::deserialize`, so the value is hashed with a new random
+/// salt, using the configuration set by `with_argon2` or the default Argon2 configuration
+/// otherwise.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Password<Hashed>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    hash_field::deserialize(deserializer)
+}
+
+/// Serializes the hashed password as its PHC string.
+pub fn serialize<S>(password: &Password<Hashed>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(password)
+}
@@ -0,0 +1,86 @@
+//! Keyed, non-reversible digests of plain text passwords.
+
+use crate::{Password, Plain};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Number of hex characters returned by `fingerprint`.
+pub const DEFAULT_FINGERPRINT_LEN: usize = 16;
+
+/// Computes the HMAC-SHA256 of the provided message with the provided key.
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length.");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// Encodes the provided bytes as lowercase hex.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+impl Password<Plain> {
+    /// Computes a keyed fingerprint of the password, suitable as a rate-limiting key.
+    ///
+    /// - `key`: The secret key of the fingerprint, distinct from any key used elsewhere.
+    ///
+    /// This is `fingerprint_with_len` with `DEFAULT_FINGERPRINT_LEN` hex characters.
+    ///
+    /// Returns the truncated, hex-encoded HMAC-SHA256 of the password.
+    pub fn fingerprint(&self, key: &[u8]) -> String {
+        self.fingerprint_with_len(key, DEFAULT_FINGERPRINT_LEN)
+    }
+
+    /// Computes a keyed fingerprint of the password truncated to the provided length.
+    ///
+    /// - `key`: The secret key of the fingerprint, distinct from any key used elsewhere.
+    /// - `len`: The number of hex characters to keep, at most 64.
+    ///
+    /// The fingerprint cannot be reversed without the key and differs from the stored hash.
+    /// Truncation trades collision resistance for size: with `n` hex characters, collisions
+    /// become likely after about `2^(2n)` distinct passwords, which is acceptable for cache or
+    /// rate-limiting keys but not for identifying passwords.
+    ///
+    /// Returns the truncated, hex-encoded HMAC-SHA256 of the password.
+    pub fn fingerprint_with_len(&self, key: &[u8], len: usize) -> String {
+        let mut fingerprint = to_hex(&hmac_sha256(key, self.as_bytes()));
+        fingerprint.truncate(len);
+        fingerprint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Password, Plain};
+
+    #[test]
+    fn fingerprint_is_deterministic() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        assert_eq!(
+            plain_password.fingerprint(b"key"),
+            plain_password.fingerprint(b"key")
+        );
+        assert_eq!(plain_password.fingerprint(b"key").len(), 16);
+        assert_eq!(plain_password.fingerprint_with_len(b"key", 8).len(), 8);
+        assert_eq!(plain_password.fingerprint_with_len(b"key", 100).len(), 64);
+        assert!(plain_password
+            .fingerprint_with_len(b"key", 64)
+            .starts_with(&plain_password.fingerprint(b"key")));
+    }
+
+    #[test]
+    fn fingerprint_depends_on_key_and_password() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        assert_ne!(
+            plain_password.fingerprint(b"key"),
+            plain_password.fingerprint(b"other")
+        );
+        assert_ne!(
+            plain_password.fingerprint(b"key"),
+            Password::<Plain>::new("Other").fingerprint(b"key")
+        );
+        assert!(!plain_password.fingerprint(b"key").contains("Password"));
+    }
+}
@@ -13,6 +13,67 @@
 //! - **Argon2 Hashing (Optional):** If the argon2 feature is enabled, the library provides hashing functionality
 //!   using Argon2, a secure password hashing algorithm.
 //!
+//! - **PHP Compatibility (Optional):** If the bcrypt feature is enabled, the `compat` module verifies bcrypt
+//!   hashes produced by PHP's `password_hash`, easing migrations from PHP applications.
+//!
+//! - **Bytes Conversion (Optional):** If the bytes feature is enabled, hashed passwords convert to and from
+//!   `bytes::Bytes` without an intermediate `String`, validating the PHC format when parsed.
+//!
+//! - **Keyed Fingerprints (Optional):** If the hmac feature is enabled, plain text passwords can produce keyed,
+//!   non-reversible HMAC-SHA256 fingerprints, for example to use as rate-limiting keys, and signed,
+//!   time-limited reset tokens.
+//!
+//! - **Lifetime Audit (Optional):** If the lifetime-audit feature is enabled, debug builds log a warning through
+//!   `tracing` when a plain text password is dropped after living longer than a configurable threshold.
+//!
+//! - **Terminal Prompts (Optional):** If the tty feature is enabled, plain text passwords can be read from the
+//!   terminal without echoing the input.
+//!
+//! - **Memory Locking (Optional):** If the mlock feature is enabled on Unix platforms, the buffers of plain text
+//!   passwords are locked in memory on a best effort basis, so they are not swapped to disk.
+//!
+//! - **Zeroization (Optional):** If the zeroize feature is enabled, passwords can be handed off with
+//!   `clone_and_scrub`, which overwrites the original buffer with zeros. Combined with mlock, locked buffers are
+//!   also overwritten before they are released.
+//!
+//! - **Whitespace Trimming (Optional):** If the trim-on-deserialize feature is enabled, surrounding whitespace
+//!   is removed from plain text passwords when they are deserialized.
+//!
+//! - **Async Streams (Optional):** If the async feature is enabled, streams of plain text passwords can be
+//!   hashed with bounded concurrency on Tokio's blocking thread pool.
+//!
+//! - **Deterministic Salt (Testing Only):** If the deterministic-salt feature is enabled, plain text passwords
+//!   can be hashed with a fixed salt for reproducible snapshot tests. Never enable it outside of tests.
+//!
+//! - **Long Password Pre-hashing (Optional):** If the prehash-long feature is enabled, passwords longer than a
+//!   configurable threshold are pre-hashed with SHA-512 before hashing and verification. The threshold becomes
+//!   part of the stored hash format and must not change once passwords are stored.
+//!
+//! - **Grapheme Lengths (Optional):** If the graphemes feature is enabled, password policies can count lengths
+//!   in grapheme clusters, matching what users perceive as characters.
+//!
+//! - **Homoglyph Detection (Optional):** If the confusables feature is enabled, plain text passwords mixing
+//!   scripts can be detected, and look-alike characters mapped to a common prototype for policy checks.
+//!
+//! - **Unicode Normalization (Optional):** If the unicode feature is enabled, plain text passwords can be
+//!   normalized to a chosen Unicode normalization form, typically NFC, before hashing and verification, or
+//!   case-folded for case-insensitive passphrase policies.
+//!
+//! - **Test Utilities (Testing Only):** If the test-util feature is enabled, plain text passwords can be hashed
+//!   with a salt derived from the password itself, yielding stable test vectors. Never enable it outside of tests.
+//!
+//! - **Verification Cache (Optional):** If the verify-cache feature is enabled, `VerifyCache` reuses recent
+//!   verification results for a short TTL. This trades brute force resistance for speed, see its security
+//!   notes before enabling it.
+//!
+//! - **OS Randomness (Optional):** If the os-rng feature is enabled, salts can be generated using the operating
+//!   system's random number generator. Without it, salt generation relies on a caller-provided random number
+//!   generator, which allows building for targets such as `wasm32-unknown-unknown`.
+//!
+//! - **Error Chaining (Default):** If the std feature is enabled, errors expose the underlying Argon2 error
+//!   through `std::error::Error::source`. Disable it together with os-rng when building for targets without
+//!   operating system randomness.
+//!
 //! ## Usage
 //!
 //! Add the library to your current project using Cargo:
@@ -24,6 +85,8 @@
 //! Then create a new password and operate on them
 //!
 //! ```rust
+//! # #[cfg(feature = "os-rng")]
+//! # {
 //! use tag_password::Password;
 //! use argon2::password_hash::SaltString;
 //! use rand_core::OsRng;
@@ -39,6 +102,7 @@
 //! hashed_password
 //!     .verify(None, plain_password)
 //!     .expect("Verification should not fail.");
+//! # }
 //! ```
 //!
 //! ## License
@@ -46,15 +110,370 @@
 //! This project is licensed under the [MIT License](LICENSE).
 
 #[cfg(feature = "argon2")]
-use argon2::{password_hash::Salt, Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::{
+    password_hash::{
+        rand_core::{CryptoRng, RngCore},
+        Salt, SaltString,
+    },
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version,
+};
 #[cfg(feature = "graphql")]
 use async_graphql::{registry::MetaType, registry::MetaTypeId, registry::Registry, InputType};
+#[cfg(feature = "os-rng")]
+use rand_core::OsRng;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "argon2")]
+use std::str::FromStr;
+#[cfg(feature = "argon2")]
+use std::time::{Duration, Instant};
 use std::{
+    borrow::Cow,
+    ffi::OsString,
     fmt::{Debug, Display},
     marker::PhantomData,
+    ops::Deref,
+};
+
+mod analysis;
+mod audit;
+pub mod backend;
+#[cfg(feature = "argon2")]
+mod builder;
+#[cfg(feature = "verify-cache")]
+mod cache;
+#[cfg(feature = "argon2")]
+mod compact;
+#[cfg(feature = "bcrypt")]
+pub mod compat;
+#[cfg(feature = "argon2")]
+mod dynamic;
+mod error;
+#[cfg(all(feature = "serde", feature = "os-rng"))]
+pub mod hash_field;
+#[cfg(all(feature = "serde", feature = "os-rng"))]
+pub mod hash_on_deserialize;
+#[cfg(feature = "hmac")]
+mod keyed;
+mod lock;
+#[cfg(feature = "argon2")]
+mod lockout;
+#[cfg(feature = "serde")]
+pub mod optional_password;
+#[cfg(feature = "argon2")]
+mod params;
+#[cfg(feature = "argon2")]
+mod parts;
+#[cfg(feature = "argon2")]
+mod passphrase;
+#[cfg(feature = "argon2")]
+mod pepper;
+mod phc;
+mod policy;
+mod prehash;
+#[cfg(feature = "argon2")]
+mod profile;
+#[cfg(feature = "argon2")]
+mod raw;
+#[cfg(feature = "argon2")]
+mod registry;
+#[cfg(feature = "hmac")]
+mod reset_token;
+pub mod sanitize;
+#[cfg(feature = "zeroize")]
+mod scrub;
+#[cfg(feature = "serde")]
+pub mod skip_password;
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "tty")]
+mod tty;
+
+pub use analysis::{Dictionary, KeyboardLayout};
+#[cfg(feature = "lifetime-audit")]
+pub use audit::{lifetime_threshold, set_lifetime_threshold};
+#[cfg(feature = "argon2")]
+pub use builder::PasswordBuilder;
+#[cfg(feature = "verify-cache")]
+pub use cache::VerifyCache;
+#[cfg(feature = "argon2")]
+pub use dynamic::DynPassword;
+#[cfg(feature = "argon2")]
+pub use error::{BuildError, HashError, LockedOrMismatch, VerifyError};
+pub use error::{InvalidUtf8Error, MultilineError};
+#[cfg(feature = "hmac")]
+pub use keyed::DEFAULT_FINGERPRINT_LEN;
+#[cfg(feature = "argon2")]
+pub use lockout::{AttemptTracker, InMemoryTracker};
+#[cfg(feature = "argon2")]
+pub use params::{analyze, plan_rehash, HashAudit, HashTarget, WeakFlag, WorkFactorStats};
+#[cfg(feature = "argon2")]
+pub use parts::StoredHashParts;
+#[cfg(feature = "argon2")]
+pub use passphrase::Wordlist;
+#[cfg(feature = "argon2")]
+pub use pepper::{Pepper, PepperedHasher};
+pub use phc::is_phc_format;
+pub use policy::{
+    CharacterClass, LengthUnit, PasswordPolicy, PolicyReport, PolicyRule, PolicyViolation,
+    RuleCheck,
 };
+#[cfg(feature = "prehash-long")]
+pub use prehash::PREHASH_THRESHOLD;
+#[cfg(feature = "argon2")]
+pub use profile::{argon2_from_env_or_default, Argon2Profile};
+#[cfg(feature = "argon2")]
+pub use registry::VerifierRegistry;
+#[cfg(feature = "hmac")]
+pub use reset_token::verify_reset_token;
+#[cfg(feature = "unicode")]
+pub use sanitize::NormalizationForm;
+
+/// Commonly used items, re-exported for convenience.
+///
+/// Importing the prelude brings the password type, its markers and, when the `argon2`
+/// feature is enabled, the Argon2 types needed to hash and verify passwords into scope
+/// with a single `use`.
+///
+/// ```rust
+/// # #[cfg(feature = "os-rng")]
+/// # {
+/// use tag_password::prelude::*;
+///
+/// let salt = generate_salt();
+/// let plain_password = Password::new("my_password");
+/// let hashed_password = plain_password
+///     .hash(Some(Argon2::default()), &salt)
+///     .expect("Hashing should not fail.");
+/// hashed_password
+///     .verify(None, plain_password)
+///     .expect("Verification should not fail.");
+/// # }
+/// ```
+pub mod prelude {
+    #[cfg(feature = "os-rng")]
+    pub use crate::generate_salt;
+    #[cfg(feature = "argon2")]
+    pub use crate::{generate_salt_with, is_hashed_format, verify_str, HashError};
+    pub use crate::{is_phc_format, HashAlgorithm, Hashed, Password, PasswordPolicy, Plain};
+    #[cfg(feature = "argon2")]
+    pub use crate::{
+        Argon2Profile, HashResult, HashTarget, Pepper, VerifierRegistry, VerifyError, WeakFlag,
+    };
+    #[cfg(feature = "argon2")]
+    pub use argon2::{password_hash::SaltString, Algorithm, Argon2, Params, Version};
+}
+
+/// Lists the hashing backends compiled into the crate.
+///
+/// This reflects the enabled cargo features, for example to log the crypto configuration of an
+/// application at startup.
+///
+/// Returns the names of the enabled backends, such as `"argon2"` and `"bcrypt"`.
+pub fn enabled_algorithms() -> &'static [&'static str] {
+    &[
+        #[cfg(feature = "argon2")]
+        "argon2",
+        #[cfg(feature = "bcrypt")]
+        "bcrypt",
+    ]
+}
+
+/// Checks whether the provided string is a well-formed PHC password hash.
+///
+/// - `value`: The string to check.
+///
+/// Returns `true` if the value parses as a PHC string, such as one produced by `hash`.
+#[cfg(feature = "argon2")]
+pub fn is_hashed_format(value: &str) -> bool {
+    PasswordHash::new(value).is_ok()
+}
+
+/// Parses a stored PHC hash string, requiring the salt and digest every stored hash has.
+///
+/// `PasswordHash::new` alone accepts strings such as `"$argon2id"` that only name an
+/// algorithm, which must not be loaded or persisted as hashes.
+#[cfg(feature = "argon2")]
+fn parse_stored_hash(value: &str) -> argon2::password_hash::Result<PasswordHash<'_>> {
+    let hash = PasswordHash::new(value)?;
+    match hash.salt.is_some() && hash.hash.is_some() {
+        true => Ok(hash),
+        false => Err(argon2::password_hash::Error::PhcStringField),
+    }
+}
+
+/// Generates a new random salt suitable for hashing passwords.
+///
+/// Returns a new `SaltString` generated with the operating system's random number generator.
+#[cfg(feature = "os-rng")]
+pub fn generate_salt() -> SaltString {
+    generate_salt_with(&mut OsRng)
+}
+
+/// Generates a new salt using the provided random number generator.
+///
+/// - `rng`: A cryptographically secure random number generator.
+///
+/// This is useful on targets where the operating system's random number generator is
+/// unavailable, such as `wasm32-unknown-unknown`, and for reproducible tests with a seeded
+/// generator.
+///
+/// Returns a new `SaltString` generated with the provided random number generator.
+#[cfg(feature = "argon2")]
+pub fn generate_salt_with<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> SaltString {
+    SaltString::generate(rng)
+}
+
+/// Maximum length, in bytes, of the input passed to Argon2 when hashing or verifying.
+///
+/// Longer passwords are rejected with `HashError::TooLong` when hashing and
+/// `VerifyError::TooLong` when verifying, to bound the cost of both. With the `prehash-long`
+/// feature enabled, the limit applies after pre-hashing, so passwords of any length are
+/// accepted since `PREHASH_THRESHOLD` is below this limit.
+#[cfg(feature = "argon2")]
+pub const MAX_HASH_INPUT_LEN: usize = 4096;
+
+/// Hashes the input passed to the key derivation function, after any pre-hashing.
+#[cfg(feature = "argon2")]
+fn hash_kdf_input(argon2: &Argon2, input: &[u8], salt: Salt) -> Result<String, HashError> {
+    check_kdf_input(input)?;
+    check_memory(argon2.params())?;
+    Ok(argon2.hash_password(input, salt)?.to_string())
+}
+
+/// Checks that the memory required by the Argon2 parameters can be allocated.
+///
+/// Argon2 aborts the process when its memory cannot be allocated, so the allocation is
+/// attempted beforehand and released, reporting a failure as `HashError::AllocationFailed`.
+#[cfg(feature = "argon2")]
+fn check_memory(params: &Params) -> Result<(), HashError> {
+    Vec::<argon2::Block>::new()
+        .try_reserve_exact(params.block_count())
+        .map_err(|_| HashError::AllocationFailed {
+            memory_kib: params.m_cost(),
+        })
+}
+
+/// Rejects empty inputs and inputs longer than `MAX_HASH_INPUT_LEN`.
+#[cfg(feature = "argon2")]
+fn check_kdf_input(input: &[u8]) -> Result<(), HashError> {
+    if input.is_empty() {
+        return Err(HashError::EmptyInput);
+    }
+    if input.len() > MAX_HASH_INPUT_LEN {
+        return Err(HashError::TooLong {
+            max: MAX_HASH_INPUT_LEN,
+        });
+    }
+    Ok(())
+}
+
+/// Applies the guards of `check_kdf_input` and `check_memory` before verifying.
+///
+/// Inputs longer than `MAX_HASH_INPUT_LEN` are rejected with `VerifyError::TooLong`, since
+/// hashing rejects them and no hash produced by this crate can match. If Argon2 parameters are
+/// provided, the memory they require is checked, and a failure is reported as
+/// `VerifyError::AllocationFailed`. Empty inputs are left to the backend, which reports them
+/// as mismatches.
+#[cfg(feature = "argon2")]
+pub(crate) fn check_verify_input(input: &[u8], params: Option<&Params>) -> Result<(), VerifyError> {
+    if input.len() > MAX_HASH_INPUT_LEN {
+        return Err(VerifyError::TooLong {
+            max: MAX_HASH_INPUT_LEN,
+        });
+    }
+    match params {
+        Some(params) => check_memory(params).map_err(|_| VerifyError::AllocationFailed {
+            memory_kib: params.m_cost(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Verifies the input passed to the key derivation function, after any pre-hashing, against
+/// a parsed hash.
+///
+/// The input is checked with `check_verify_input` first, using the stored parameters of
+/// Argon2 hashes.
+#[cfg(feature = "argon2")]
+pub(crate) fn verify_kdf_input(
+    verifier: &dyn PasswordVerifier,
+    input: &[u8],
+    hash: &PasswordHash,
+) -> Result<bool, VerifyError> {
+    let params = Algorithm::try_from(hash.algorithm)
+        .ok()
+        .and_then(|_| Params::try_from(hash).ok());
+    check_verify_input(input, params.as_ref())?;
+    match verifier.verify_password(input, hash) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(error) => Err(VerifyError::Backend(error)),
+    }
+}
+
+/// Verifies like `verify_kdf_input`, for methods returning an `argon2::password_hash::Result`.
+///
+/// Mismatches and too long inputs are both reported as `Error::Password`, as Argon2 does for
+/// passwords it cannot accept, and allocation failures as an invalid parameter, as Argon2
+/// does for a memory cost it cannot accept.
+#[cfg(feature = "argon2")]
+pub(crate) fn verify_kdf_input_phc(
+    verifier: &dyn PasswordVerifier,
+    input: &[u8],
+    hash: &PasswordHash,
+) -> argon2::password_hash::Result<()> {
+    match verify_kdf_input(verifier, input, hash) {
+        Ok(true) => Ok(()),
+        Ok(false) | Err(VerifyError::Mismatch | VerifyError::TooLong { .. }) => {
+            Err(argon2::password_hash::Error::Password)
+        }
+        Err(VerifyError::AllocationFailed { .. }) => {
+            Err(argon2::password_hash::errors::InvalidValue::TooLong.param_error())
+        }
+        Err(VerifyError::InvalidHash(error) | VerifyError::Backend(error)) => Err(error),
+    }
+}
+
+/// Characters used when generating random passwords.
+#[cfg(feature = "argon2")]
+const PASSWORD_CHARSET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&*+-=?@^_~";
+
+/// Picks a uniformly distributed index below `len`, which must be between 1 and `u32::MAX`.
+#[cfg(feature = "argon2")]
+fn random_index<R: RngCore + CryptoRng + ?Sized>(rng: &mut R, len: usize) -> usize {
+    let len = len as u32;
+    let zone = u32::MAX - u32::MAX % len;
+    loop {
+        let sample = rng.next_u32();
+        if sample < zone {
+            break (sample % len) as usize;
+        }
+    }
+}
+
+/// Compares two byte slices of equal length in constant time.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Proof that a value was produced by hashing a password with a key derivation function.
+///
+/// A proof can only be obtained from `Password::hash_proof` and is consumed by
+/// `Password::into_hashed`. It holds the hash produced by `hash_proof` and a private seal, so
+/// it cannot be constructed from arbitrary data. The `tests/ui` compile tests check this.
+#[cfg(feature = "argon2")]
+#[derive(Debug)]
+pub struct HashProof {
+    hashed: Password<Hashed>,
+    _seal: Seal,
+}
+
+/// Zero-sized seal keeping `HashProof` from being constructed outside of the crate.
+#[cfg(feature = "argon2")]
+#[derive(Debug)]
+struct Seal;
 
 /// Marker type indicating a hashed password.
 ///
@@ -75,41 +494,280 @@ pub struct Hashed;
 #[derive(Clone, PartialEq, Eq)]
 pub struct Plain;
 
+mod sealed {
+    /// Marker types a `Password` can be tagged with.
+    ///
+    /// The trait is sealed, so no other markers can be defined outside of the crate.
+    pub trait Marker {
+        /// Whether the marker indicates a plain text password.
+        const PLAIN: bool;
+    }
+
+    impl Marker for super::Plain {
+        const PLAIN: bool = true;
+    }
+
+    impl Marker for super::Hashed {
+        const PLAIN: bool = false;
+    }
+}
+
 /// Represents password data that can be hashed and verified.
 ///
 /// This struct encapsulates password data and supports hashing with the `hash` function,
 /// which can be enabled with the `argon2` feature. It also provides verification
 /// functionality via the `verify` function.
-#[derive(Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Password<T: ?Sized>(PhantomData<T>, String);
+///
+/// Hashed passwords created through `FromStr` or `TryFrom<&str>` additionally cache the
+/// detected `HashAlgorithm`, so `algorithm` does not need to inspect the value again.
+pub struct Password<T: ?Sized>(
+    PhantomData<T>,
+    lock::LockedString,
+    Option<HashAlgorithm>,
+    #[allow(dead_code)] audit::Audit,
+);
 
-impl<T: ?Sized> Password<T> {
-    /// Creates a new `Password` instance from the provided value.
+/// Password hashing algorithm identified from a hash string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+    /// Argon2d, identified by the `$argon2d$` prefix.
+    Argon2d,
+    /// Argon2i, identified by the `$argon2i$` prefix.
+    Argon2i,
+    /// Argon2id, identified by the `$argon2id$` prefix.
+    Argon2id,
+    /// bcrypt, identified by the `$2a$`, `$2b$`, `$2x$` or `$2y$` prefixes.
+    Bcrypt,
+    /// scrypt, identified by the `$scrypt$` prefix.
+    Scrypt,
+    /// PBKDF2, identified by the `$pbkdf2` prefix.
+    Pbkdf2,
+}
+
+impl HashAlgorithm {
+    /// Detects the algorithm of a hash string from its prefix.
     ///
-    /// # Arguments
+    /// - `value`: The hash string to inspect.
     ///
-    /// * `value`: A value that can be converted into a `String`.
+    /// Returns the detected `HashAlgorithm`, or `None` if the prefix is not recognized.
+    pub fn detect(value: &str) -> Option<Self> {
+        let ident = value.strip_prefix('$')?.split('$').next()?;
+        match ident {
+            "argon2d" => Some(HashAlgorithm::Argon2d),
+            "argon2i" => Some(HashAlgorithm::Argon2i),
+            "argon2id" => Some(HashAlgorithm::Argon2id),
+            "2a" | "2b" | "2x" | "2y" => Some(HashAlgorithm::Bcrypt),
+            "scrypt" => Some(HashAlgorithm::Scrypt),
+            _ if ident.starts_with("pbkdf2") => Some(HashAlgorithm::Pbkdf2),
+            _ => None,
+        }
+    }
+}
+
+/// Result of hashing a password, recording the salt that was used.
+///
+/// This is returned by `hash_with_salt_record` and allows storing the salt separately
+/// from the hash without parsing it back out of the PHC string.
+#[cfg(feature = "argon2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashResult {
+    /// The hashed password.
+    pub hash: Password<Hashed>,
+    /// The salt used to produce the hash.
+    pub salt: SaltString,
+}
+
+impl<T: sealed::Marker + ?Sized> Password<T> {
+    /// Creates a new `Password` instance from the provided value, without checking it.
+    ///
+    /// - `value`: A value that can be converted into a `String`.
+    ///
+    /// Hashed passwords built this way are not validated, so this is only used for values
+    /// produced by a key derivation function or already validated as PHC strings.
     ///
     /// Returns a new `Password` instance with the provided value.
-    pub fn new(value: impl Into<String>) -> Self {
-        Password(Default::default(), value.into())
+    pub(crate) fn new_unchecked(value: impl Into<String>) -> Self {
+        Password::with_algorithm(value.into(), None)
+    }
+
+    /// Creates a new `Password` instance from its value and cached algorithm.
+    ///
+    /// Every constructor goes through this function, so all fields are initialized in one
+    /// place.
+    fn with_algorithm(value: String, algorithm: Option<HashAlgorithm>) -> Self {
+        Password(
+            Default::default(),
+            lock::LockedString::new::<T>(value),
+            algorithm,
+            audit::Audit::start::<T>(),
+        )
     }
+}
 
+impl<T: ?Sized> Password<T> {
     /// Retrieves the byte representation of the password value.
     ///
     /// Returns a slice containing the bytes representing the password.
     pub fn as_bytes(&self) -> &[u8] {
         self.1.as_bytes()
     }
+
+    /// Retrieves the raw inner string of the password value, regardless of its marker.
+    ///
+    /// This is the explicit escape hatch for cases such as custom storage. For a
+    /// `Password<Plain>`, the returned value is the plain text password itself, so it must
+    /// never end up in logs or responses. `Display` and `Debug` stay redacted.
+    ///
+    /// Returns a string slice containing the password value.
+    pub fn raw(&self) -> &str {
+        &self.1
+    }
+
+    /// Retrieves a representation of the password that is safe to include in logs.
+    ///
+    /// The value is the same placeholder regardless of the marker or the password, for example
+    /// `tracing::info!(password = password.log_value(), "user created")`.
+    ///
+    /// Returns a static placeholder string.
+    pub fn log_value(&self) -> &'static str {
+        REDACTED
+    }
 }
 
 impl Password<Plain> {
-    /// Converts a plain text password into a hashed password.
+    /// Creates a new `Password` instance from the provided value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: A value that can be converted into a `String`.
+    ///
+    /// Hashed passwords cannot be created from arbitrary values. Obtain them by hashing, or
+    /// by parsing a stored PHC string with `Password::<Hashed>::from_phc`, `FromStr` or
+    /// `Deserialize`.
+    ///
+    /// Returns a new `Password` instance with the provided value.
+    pub fn new(value: impl Into<String>) -> Self {
+        Password::new_unchecked(value)
+    }
+
+    /// Creates a plain text password from a hex-encoded hash computed by the client.
+    ///
+    /// - `value`: The hex-encoded client-side hash, such as a SHA-256 digest of the password.
+    ///
+    /// Some clients pre-hash passwords before sending them to reduce plain text exposure on the
+    /// wire. The server then treats the pre-hash as the plain text input of `hash` and `verify`,
+    /// so both must be called with values produced by this constructor. The hex digits are
+    /// lowercased, so clients may send either case. The pre-hash alone authenticates the user,
+    /// so it must be protected exactly like a plain text password.
+    ///
+    /// Returns a new `Password` instance containing the normalized pre-hash.
+    pub fn from_prehashed_hex(value: &str) -> Self {
+        Password::new(value.trim().to_ascii_lowercase())
+    }
+
+    /// Creates a plain text password from a single line of input.
+    ///
+    /// - `value`: The line read from the input, without its terminating line break.
+    ///
+    /// When passwords are read from line-based input, such as a file or standard input, an
+    /// embedded line break usually indicates a parsing bug. Use `new` for other inputs.
+    ///
+    /// Produces a result containing the password if successful. If the value contains `\n` or
+    /// `\r`, a `MultilineError` is returned.
+    pub fn new_single_line(value: impl Into<String>) -> Result<Self, MultilineError> {
+        let value = value.into();
+        if value.contains(['\n', '\r']) {
+            return Err(MultilineError);
+        }
+        Ok(Password::new(value))
+    }
+
+    /// Creates a plain text password by joining fragments, such as the words of a passphrase
+    /// collected through separate inputs.
+    ///
+    /// - `fragments`: The fragments of the password, in order.
+    /// - `separator`: The separator inserted between fragments, such as `" "`.
+    ///
+    /// The fragments are used as is. Use the same separator whenever the password is set or
+    /// verified, otherwise the resulting passwords never match.
+    ///
+    /// Returns a new `Password` instance containing the joined fragments.
+    pub fn from_fragments(fragments: &[&str], separator: &str) -> Self {
+        Password::new(fragments.join(separator))
+    }
+
+    /// Checks whether the confirmation matches the password, such as the two password fields of
+    /// a signup form.
+    ///
+    /// - `confirmation`: The confirmation typed by the user.
+    ///
+    /// The passwords are compared in constant time. Only their lengths, which differ whenever
+    /// the comparison ends early, may leak through timing.
+    ///
+    /// Returns `true` if both passwords are identical.
+    pub fn confirm(&self, confirmation: &Password<Plain>) -> bool {
+        constant_time_eq(self.as_bytes(), confirmation.as_bytes())
+    }
+
+    /// Generates a new random plain text password.
+    ///
+    /// - `length`: The number of characters in the generated password.
+    ///
+    /// Returns a new `Password` instance generated with the operating system's random number
+    /// generator.
+    #[cfg(feature = "os-rng")]
+    pub fn generate(length: usize) -> Self {
+        Self::generate_with_rng(&mut OsRng, length)
+    }
+
+    /// Generates a new random plain text password using the provided random number generator.
+    ///
+    /// - `rng`: A cryptographically secure random number generator.
+    /// - `length`: The number of characters in the generated password.
+    ///
+    /// Characters are picked uniformly from ASCII letters, digits and common symbols.
+    ///
+    /// Returns a new `Password` instance containing the generated password.
+    #[cfg(feature = "argon2")]
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R, length: usize) -> Self {
+        let value = (0..length)
+            .map(|_| PASSWORD_CHARSET[random_index(rng, PASSWORD_CHARSET.len())] as char)
+            .collect::<String>();
+        Password::new(value)
+    }
+
+    /// Hashes the password, producing a `HashProof` instead of a hashed password.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing the proof if successful, which `into_hashed` turns into a
+    /// hashed password. If the password is empty or too long, or hashing fails, a `HashError`
+    /// is returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_proof<'a>(
+        &self,
+        argon2: Option<Argon2>,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<HashProof, HashError> {
+        self.hash(argon2, salt).map(|hashed| HashProof {
+            hashed,
+            _seal: Seal,
+        })
+    }
+
+    /// Converts the plain text password into a hashed password, consuming the plain text.
     ///
-    /// Returns a new `Password` instance containing the hashed password.
-    pub unsafe fn as_hashed(self) -> Password<Hashed> {
-        Password::new(self.1)
+    /// - `proof`: The proof produced by hashing the password with `hash_proof`.
+    ///
+    /// `HashProof` values can only be produced by hashing and hold the resulting hash, so a
+    /// hashed password cannot be fabricated from arbitrary data this way.
+    ///
+    /// Returns a new `Password` instance containing the hash held by the proof.
+    #[cfg(feature = "argon2")]
+    pub fn into_hashed(self, proof: HashProof) -> Password<Hashed> {
+        proof.hashed
     }
 
     /// Hashes the password using Argon2 (if the 'argon2' feature is enabled).
@@ -118,70 +776,485 @@ impl Password<Plain> {
     /// - `salt`: A salt value used for hashing.
     ///
     /// Produces a result containing a new `Password` instance with the hashed value if successful.
-    /// If hashing fails, an `argon2::password_hash::Result` with an error is returned.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
     #[cfg(feature = "argon2")]
     pub fn hash<'a>(
         &self,
         argon2: Option<Argon2>,
         salt: impl Into<Salt<'a>>,
-    ) -> argon2::password_hash::Result<Password<Hashed>> {
-        let v = self.1.as_bytes();
-        Ok(Password::new(
-            argon2
-                .unwrap_or_default()
-                .hash_password(v, salt)?
-                .to_string(),
-        ))
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash_shared(&argon2.unwrap_or_default(), salt)
     }
-}
 
-impl Password<Hashed> {
-    /// Unsafely converts a hashed password into a plain text password.
-    /// This operation is marked as unsafe because once a password is hashed,
-    /// it cannot be converted back to plain text.
+    /// Hashes the password using a borrowed Argon2 configuration.
     ///
-    /// Returns a new `Password` instance containing the plain text password.
-    pub unsafe fn as_plain(self) -> Password<Plain> {
-        Password::new(self.1)
+    /// - `argon2`: The `Argon2` configuration, such as one shared through an `Arc`.
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_shared<'a>(
+        &self,
+        argon2: &Argon2,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        hash_kdf_input(argon2, &prehash::kdf_input(self.1.as_bytes()), salt.into())
+            .map(Password::new_unchecked)
     }
 
-    /// Verifies if the hashed password matches the provided plain text password.
+    /// Verifies the password against a hash that was already parsed.
     ///
     /// - `argon2`: An optional `Argon2` configuration.
-    /// - `plain`: A plain text password used for verification.
+    /// - `parsed`: The stored hash, parsed with `PasswordHash::new`.
     ///
-    /// Returns a result indicating success or failure of the verification process.
-    /// If successful, `Ok(())` is returned. If verification fails, an
-    /// `argon2::password_hash::Result` with an error is returned.
-
-    pub fn verify(
+    /// This avoids parsing the same PHC string again when it is verified against many
+    /// candidate passwords, such as in a dictionary test of stored hashes.
+    ///
+    /// Returns a result indicating success or failure of the verification process, as
+    /// `Password::<Hashed>::verify`.
+    #[cfg(feature = "argon2")]
+    pub fn verify_against(
         &self,
         argon2: Option<Argon2>,
-        plain: impl Into<Password<Plain>>,
+        parsed: &PasswordHash,
     ) -> argon2::password_hash::Result<()> {
-        argon2
-            .unwrap_or_default()
-            .verify_password(plain.into().as_bytes(), &PasswordHash::new(&self.1)?)
+        verify_kdf_input_phc(
+            &argon2.unwrap_or_default(),
+            &prehash::kdf_input(self.1.as_bytes()),
+            parsed,
+        )
     }
-}
 
-impl<T: ?Sized> Into<String> for Password<T> {
-    /// Converts the `Password` instance into a `String`.
-    fn into(self) -> String {
-        self.1
+    /// Hashes the password using Argon2id with the default parameters.
+    ///
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_argon2id<'a>(
+        &self,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash_with_algorithm(Algorithm::Argon2id, salt)
     }
-}
 
-#[cfg(feature = "graphql")]
-impl<T: ?Sized + Send + Sync> InputType for Password<T> {
-    type RawValueType = String;
-
-    fn type_name() -> std::borrow::Cow<'static, str> {
+    /// Hashes the password using Argon2i with the default parameters.
+    ///
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_argon2i<'a>(
+        &self,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash_with_algorithm(Algorithm::Argon2i, salt)
+    }
+
+    /// Hashes the password using Argon2d with the default parameters.
+    ///
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_argon2d<'a>(
+        &self,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash_with_algorithm(Algorithm::Argon2d, salt)
+    }
+
+    #[cfg(feature = "argon2")]
+    fn hash_with_algorithm<'a>(
+        &self,
+        algorithm: Algorithm,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash_shared(
+            &Argon2::new(algorithm, Version::default(), Params::default()),
+            salt,
+        )
+    }
+
+    /// Hashes the password with a freshly generated salt, typically when loading configuration.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    ///
+    /// This is meant for plain text values provided at load time, such as an initial admin
+    /// password in a configuration file, so that only the hash is kept afterwards.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "os-rng")]
+    pub fn into_hashed_on_load(
+        self,
+        argon2: Option<Argon2>,
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash(argon2, &generate_salt())
+    }
+
+    /// Hashes the password and records the salt that was used.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `salt`: The salt used for hashing, which is recorded in the result.
+    ///
+    /// Produces a result containing a `HashResult` with the hashed password and its salt if
+    /// successful. If the password is empty or too long, or hashing fails, a `HashError` is
+    /// returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_with_salt_record(
+        &self,
+        argon2: Option<Argon2>,
+        salt: &SaltString,
+    ) -> Result<HashResult, HashError> {
+        Ok(HashResult {
+            hash: self.hash(argon2, salt)?,
+            salt: salt.clone(),
+        })
+    }
+
+    /// Hashes the password and immediately verifies it against the produced hash.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// This self-check catches misconfigured parameters early, at the cost of running two
+    /// Argon2 operations: one to hash and one to verify.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if both
+    /// steps succeed. If the password is empty or too long, or hashing or verification fails, a
+    /// `HashError` is returned.
+    #[cfg(feature = "argon2")]
+    pub fn hash_checked_roundtrip<'a>(
+        &self,
+        argon2: Option<Argon2>,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<Password<Hashed>, HashError> {
+        let argon2 = argon2.unwrap_or_default();
+        let hashed = self.hash(Some(argon2.clone()), salt)?;
+        argon2.verify_password(
+            &prehash::kdf_input(self.as_bytes()),
+            &PasswordHash::new(&hashed.1).map_err(HashError::Backend)?,
+        )?;
+        Ok(hashed)
+    }
+}
+
+impl Password<Hashed> {
+    /// Creates a hashed password from a stored PHC string, caching its algorithm.
+    ///
+    /// - `value`: The PHC string, such as one loaded from a database.
+    ///
+    /// This is the checked replacement for building a `Password<Hashed>` with `Password::new`
+    /// or `From<String>`, which only create plain text passwords. The value is kept as is;
+    /// use `FromStr` to trim surrounding whitespace first.
+    ///
+    /// Produces a result containing the hashed password if the value is a well-formed PHC
+    /// string with a salt and digest. Otherwise, an `argon2::password_hash::Result` with an
+    /// error is returned.
+    #[cfg(feature = "argon2")]
+    pub fn from_phc(value: impl Into<String>) -> argon2::password_hash::Result<Self> {
+        let value = value.into();
+        parse_stored_hash(&value)?;
+        let algorithm = HashAlgorithm::detect(&value);
+        Ok(Password::with_algorithm(value, algorithm))
+    }
+
+    /// Relabels a hashed password as plain text, keeping the hash string as its value.
+    ///
+    /// A hash cannot be converted back to the password it was produced from. This is only
+    /// meant for cases that use the hash string as an input, such as wrapping legacy hashes in
+    /// a new hash during a migration.
+    ///
+    /// Returns a new `Password` instance containing the hash string.
+    ///
+    /// # Safety
+    ///
+    /// This function is memory safe. Callers must not use the result as the user's password,
+    /// such as verifying user input against it, since it holds the hash rather than the
+    /// password.
+    pub unsafe fn as_plain(self) -> Password<Plain> {
+        Password::new(self.1.into_string())
+    }
+
+    /// Retrieves the algorithm used to produce the hashed password.
+    ///
+    /// The algorithm cached when the value was parsed is returned as is; otherwise it is
+    /// detected from the prefix of the hash.
+    ///
+    /// Returns the `HashAlgorithm`, or `None` if it could not be identified.
+    pub fn algorithm(&self) -> Option<HashAlgorithm> {
+        self.2.or_else(|| HashAlgorithm::detect(&self.1))
+    }
+
+    /// Returns `true` if the password was hashed with any Argon2 variant.
+    pub fn is_argon2(&self) -> bool {
+        matches!(
+            self.algorithm(),
+            Some(HashAlgorithm::Argon2d | HashAlgorithm::Argon2i | HashAlgorithm::Argon2id)
+        )
+    }
+
+    /// Returns `true` if the password was hashed with bcrypt, including PHP's `$2y$` variant.
+    pub fn is_bcrypt(&self) -> bool {
+        self.algorithm() == Some(HashAlgorithm::Bcrypt)
+    }
+
+    /// Returns `true` if the password was hashed with scrypt.
+    pub fn is_scrypt(&self) -> bool {
+        self.algorithm() == Some(HashAlgorithm::Scrypt)
+    }
+
+    /// Verifies if the hashed password matches the provided plain text password.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Returns a result indicating success or failure of the verification process.
+    /// If successful, `Ok(())` is returned. If verification fails, an
+    /// `argon2::password_hash::Result` with an error is returned, `Error::Password` being used
+    /// both for a wrong password and one longer than `MAX_HASH_INPUT_LEN`.
+    #[cfg(feature = "argon2")]
+    pub fn verify(
+        &self,
+        argon2: Option<Argon2>,
+        plain: impl Into<Password<Plain>>,
+    ) -> argon2::password_hash::Result<()> {
+        self.verify_shared(&argon2.unwrap_or_default(), plain)
+    }
+
+    /// Verifies the hashed password, taking at least the provided duration.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    /// - `min`: The minimum duration of the call.
+    ///
+    /// The real verification time is measured and the current thread sleeps for the remainder
+    /// if it finished early, which flattens timing differences between malformed hashes that
+    /// fail immediately and real verifications. Choose `min` above the usual verification time.
+    ///
+    /// Returns a result indicating success or failure of the verification process, as `verify`.
+    #[cfg(feature = "argon2")]
+    pub fn verify_min_duration(
+        &self,
+        argon2: Option<Argon2>,
+        plain: impl Into<Password<Plain>>,
+        min: Duration,
+    ) -> argon2::password_hash::Result<()> {
+        let started = Instant::now();
+        let result = self.verify(argon2, plain);
+        if let Some(remaining) = min.checked_sub(started.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+        result
+    }
+
+    /// Verifies the hashed password, handing the plain text password back in every case.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// This avoids cloning the plain text password in flows that still need it after
+    /// verification, such as rehashing it with stronger parameters.
+    ///
+    /// Returns `Ok` with the plain text password if it matches. Otherwise, the plain text
+    /// password is returned alongside `VerifyError::Mismatch` for a wrong password,
+    /// `VerifyError::InvalidHash` if the hash cannot be parsed, and `VerifyError::Backend` if
+    /// the backend fails.
+    #[cfg(feature = "argon2")]
+    pub fn verify_returning(
+        &self,
+        argon2: Option<Argon2>,
+        plain: Password<Plain>,
+    ) -> Result<Password<Plain>, (Password<Plain>, VerifyError)> {
+        match self.verify_bytes(argon2, plain.as_bytes()) {
+            Ok(true) => Ok(plain),
+            Ok(false) => Err((plain, VerifyError::Mismatch)),
+            Err(error) => Err((plain, error)),
+        }
+    }
+
+    /// Verifies if the hashed password matches the provided plain text password using a
+    /// borrowed Argon2 configuration.
+    ///
+    /// - `argon2`: The `Argon2` configuration, such as one shared through an `Arc`.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Returns a result indicating success or failure of the verification process.
+    /// If successful, `Ok(())` is returned. If verification fails, an
+    /// `argon2::password_hash::Result` with an error is returned.
+    #[cfg(feature = "argon2")]
+    pub fn verify_shared(
+        &self,
+        argon2: &Argon2,
+        plain: impl Into<Password<Plain>>,
+    ) -> argon2::password_hash::Result<()> {
+        verify_kdf_input_phc(
+            argon2,
+            &prehash::kdf_input(plain.into().as_bytes()),
+            &PasswordHash::new(&self.1)?,
+        )
+    }
+
+    /// Checks whether the hashed password matches the provided plain text password.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Unlike `verify`, a wrong password is not reported as an error: `Ok(true)` is returned
+    /// when the passwords match and `Ok(false)` when they do not. A `VerifyError` is returned
+    /// only if the password is longer than `MAX_HASH_INPUT_LEN`, the stored hash cannot be
+    /// parsed or the backend fails.
+    #[cfg(feature = "argon2")]
+    pub fn try_verify(
+        &self,
+        argon2: Option<Argon2>,
+        plain: impl Into<Password<Plain>>,
+    ) -> Result<bool, VerifyError> {
+        self.verify_bytes(argon2, plain.into().as_bytes())
+    }
+
+    /// Checks whether any of several variants of a plain text password matches the hashed
+    /// password.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `variants`: Variants of the submitted password, such as its NFC, NFD and trimmed
+    ///   forms when migrating hashes created with inconsistent normalization.
+    ///
+    /// Every variant is verified, even after a match, so the total time only depends on the
+    /// number of variants and not on which one matched. Each variant costs a full hash
+    /// computation, so keep the list short.
+    ///
+    /// Returns `Ok(true)` if any variant matches, `Ok(false)` otherwise, and a `VerifyError`
+    /// if the stored hash cannot be parsed or the backend fails.
+    #[cfg(feature = "argon2")]
+    pub fn verify_variants(
+        &self,
+        argon2: Option<Argon2>,
+        variants: &[Password<Plain>],
+    ) -> Result<bool, VerifyError> {
+        let argon2 = argon2.unwrap_or_default();
+        let mut matched = false;
+        for variant in variants {
+            matched |= self.verify_bytes(Some(argon2.clone()), variant.as_bytes())?;
+        }
+        Ok(matched)
+    }
+
+    /// Checks whether a candidate password is the same as this hashed password.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `candidate`: The plain text password to compare against, such as a new password
+    ///   submitted in a password change flow.
+    ///
+    /// This behaves like `try_verify` and is named to express intent when rejecting password
+    /// reuse. The comparison of the derived hashes is performed in constant time.
+    ///
+    /// Returns `Ok(true)` if the candidate matches, `Ok(false)` otherwise, and a `VerifyError`
+    /// if the stored hash cannot be parsed or the backend fails.
+    #[cfg(feature = "argon2")]
+    pub fn is_same_as(
+        &self,
+        argon2: Option<Argon2>,
+        candidate: &Password<Plain>,
+    ) -> Result<bool, VerifyError> {
+        self.verify_bytes(argon2, candidate.as_bytes())
+    }
+
+    /// Checks whether the hashed password matches the provided plain text password.
+    ///
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Verification uses the default `Argon2` configuration and compares the derived hashes in
+    /// constant time. This is not a `PartialEq` implementation between `Password<Hashed>` and
+    /// `Password<Plain>` because verification depends on an Argon2 configuration and can fail,
+    /// neither of which fits `PartialEq`, and because equality is expected to be cheap and
+    /// symmetric. Use `try_verify` to distinguish mismatches from errors.
+    ///
+    /// Returns `true` if the passwords match, and `false` if they do not or verification fails.
+    #[cfg(feature = "argon2")]
+    pub fn matches(&self, plain: &Password<Plain>) -> bool {
+        matches!(self.verify_bytes(None, plain.as_bytes()), Ok(true))
+    }
+
+    /// Checks whether two hashed passwords are byte-identical.
+    ///
+    /// - `other`: The hashed password to compare against.
+    ///
+    /// Two hashes of the same password produced with different salts or parameters share no
+    /// bytes, and deciding whether they represent the same password would require the plain
+    /// text one. This method therefore never claims a match across salts: it only recognises
+    /// a hash compared with a copy of itself. Use `verify` with the plain text password to
+    /// check a password against a hash.
+    ///
+    /// Returns `true` if both hashes are byte-identical, and `false` otherwise, even when they
+    /// may have been produced from the same password.
+    pub fn could_match(&self, other: &Password<Hashed>) -> bool {
+        *self.1 == *other.1
+    }
+
+    #[cfg(feature = "argon2")]
+    fn verify_bytes(&self, argon2: Option<Argon2>, plain: &[u8]) -> Result<bool, VerifyError> {
+        verify_phc(&self.1, argon2, plain)
+    }
+}
+
+/// Verifies a plain text password against a stored hash string in one step.
+///
+/// - `hash`: The stored PHC hash string, such as one read from a database.
+/// - `plain`: The submitted plain text password.
+/// - `argon2`: An optional `Argon2` configuration.
+///
+/// Returns `Ok(())` if the password matches. Otherwise, `VerifyError::Mismatch` is returned
+/// for a wrong password, `VerifyError::TooLong` if it is longer than `MAX_HASH_INPUT_LEN`,
+/// `VerifyError::InvalidHash` if the hash cannot be parsed, and `VerifyError::Backend` if the
+/// backend fails.
+#[cfg(feature = "argon2")]
+pub fn verify_str(hash: &str, plain: &str, argon2: Option<Argon2>) -> Result<(), VerifyError> {
+    match verify_phc(hash, argon2, plain.as_bytes())? {
+        true => Ok(()),
+        false => Err(VerifyError::Mismatch),
+    }
+}
+
+#[cfg(feature = "argon2")]
+fn verify_phc(hash: &str, argon2: Option<Argon2>, plain: &[u8]) -> Result<bool, VerifyError> {
+    let hash = PasswordHash::new(hash).map_err(VerifyError::InvalidHash)?;
+    verify_kdf_input(
+        &argon2.unwrap_or_default(),
+        &prehash::kdf_input(plain),
+        &hash,
+    )
+}
+
+impl<T: ?Sized> From<Password<T>> for String {
+    /// Converts the `Password` instance into a `String`.
+    fn from(value: Password<T>) -> Self {
+        value.1.into_string()
+    }
+}
+
+impl<T: ?Sized> From<Password<T>> for Cow<'static, str> {
+    /// Converts the `Password` instance into an owned `Cow`, without copying its value.
+    fn from(value: Password<T>) -> Self {
+        Cow::Owned(value.1.into_string())
+    }
+}
+
+#[cfg(feature = "graphql")]
+impl InputType for Password<Plain> {
+    type RawValueType = String;
+
+    fn type_name() -> std::borrow::Cow<'static, str> {
         std::borrow::Cow::Borrowed("Password")
     }
 
     fn create_type_info(registry: &mut Registry) -> String {
-        registry.create_input_type::<Password<T>, _>(MetaTypeId::Scalar, |_| MetaType::Scalar {
+        registry.create_input_type::<Password<Plain>, _>(MetaTypeId::Scalar, |_| MetaType::Scalar {
             name: "Password".into(),
             description: Some("A type used internally to represent a password.".into()),
             is_valid: None,
@@ -212,45 +1285,1162 @@ impl<T: ?Sized + Send + Sync> InputType for Password<T> {
     }
 }
 
-impl<T: ?Sized> From<String> for Password<T> {
-    /// Converts the `String` instance to a `Password`.
-    fn from(value: String) -> Self {
-        Password(Default::default(), value)
+#[cfg(feature = "serde")]
+impl Serialize for Password<Plain> {
+    /// Serializes the plain text `Password` as its inner string.
+    ///
+    /// Use `skip_password::should_skip` to omit password fields from serialized output.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.1)
     }
 }
 
-impl<T: ?Sized> Debug for Password<T> {
-    /// Formats the `Password` for debugging purposes.
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.1)
+#[cfg(feature = "serde")]
+impl Serialize for Password<Hashed> {
+    /// Serializes the hashed `Password`.
+    ///
+    /// Human-readable formats such as JSON receive the PHC string. With the `argon2` feature
+    /// enabled, other formats such as bincode receive a compact structure holding the raw salt
+    /// and hash bytes instead of their Base64 encoding.
+    ///
+    /// With the `argon2` feature enabled, serialization fails if the value is not a well-formed
+    /// PHC string with a salt and digest, so corrupt values are never persisted.
+    ///
+    /// Use `skip_password::should_skip` to omit password fields from serialized output.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[cfg(feature = "argon2")]
+        {
+            if !serializer.is_human_readable() {
+                return compact::CompactHash::from_phc(&self.1)
+                    .map_err(serde::ser::Error::custom)?
+                    .serialize(serializer);
+            }
+            parse_stored_hash(&self.1).map_err(serde::ser::Error::custom)?;
+        }
+        serializer.serialize_str(&self.1)
     }
 }
 
-impl<T: ?Sized> Display for Password<T> {
-    /// Formats the `Password` for displaying purposes.
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.1)
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Password<Plain> {
+    /// Deserializes a plain text `Password` from a string.
+    ///
+    /// With the `trim-on-deserialize` feature enabled, surrounding whitespace is removed, which
+    /// helps with trailing spaces added by form autofill. Spaces can be an intentional part of
+    /// a password, so the feature is disabled by default. When enabled, any password set with
+    /// surrounding spaces becomes a different, shorter password, and values hashed before
+    /// enabling it may no longer verify.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        #[cfg(feature = "trim-on-deserialize")]
+        let value = value.trim().to_owned();
+        Ok(Password::new(value))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use argon2::password_hash::SaltString;
-    use rand_core::OsRng;
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Password<Hashed> {
+    /// Deserializes a hashed `Password` from a string, or from the compact structure written by
+    /// `Serialize` for formats that are not human-readable.
+    ///
+    /// With the `argon2` feature enabled, the value must be a well-formed PHC string with a
+    /// salt and digest, as with `from_phc`, so plain text or empty values are rejected
+    /// instead of being wrapped as hashes. Both representations are validated the same way.
+    /// Use `hash_on_deserialize` to accept plain text values and hash them.
+    ///
+    /// Parameters are not interpreted, so hashes with parameters unknown to this version of the
+    /// crate still deserialize. They are validated when the hash is verified.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[cfg(feature = "argon2")]
+        if !deserializer.is_human_readable() {
+            return compact::CompactHash::deserialize(deserializer)?
+                .to_phc()
+                .and_then(Password::from_phc)
+                .map_err(|error| {
+                    serde::de::Error::custom(format_args!("expected a PHC password hash: {error}"))
+                });
+        }
 
-    use crate::Password;
+        let value = String::deserialize(deserializer)?;
+        #[cfg(feature = "argon2")]
+        return Password::from_phc(value).map_err(|error| {
+            serde::de::Error::custom(format_args!("expected a PHC password hash: {error}"))
+        });
+        #[cfg(not(feature = "argon2"))]
+        Ok(Password::new_unchecked(value))
+    }
+}
 
-    #[test]
-    fn argon_encoding_decoding() {
-        let plain_password = Password::new("Password");
-        let salt = SaltString::generate(&mut OsRng);
+#[cfg(feature = "argon2")]
+impl FromStr for Password<Hashed> {
+    type Err = argon2::password_hash::Error;
 
-        let argon_encoded_password = plain_password
-            .hash(None, &salt)
-            .expect("Argon2 encoding should not fail.");
+    /// Parses a PHC string into a hashed `Password`, caching its algorithm.
+    ///
+    /// Surrounding whitespace, such as a trailing newline appended by a database or config
+    /// file, is trimmed before parsing and not kept in the value.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Password::from_phc(value.trim().to_owned())
+    }
+}
 
-        argon_encoded_password
-            .verify(None, plain_password)
-            .expect("Argon2 encoded password verification should not fail.");
+#[cfg(feature = "argon2")]
+impl TryFrom<&str> for Password<Hashed> {
+    type Error = argon2::password_hash::Error;
+
+    /// Parses a PHC string into a hashed `Password`, caching its algorithm.
+    ///
+    /// Surrounding whitespace is trimmed before parsing, as with `FromStr`.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<Password<Hashed>> for bytes::Bytes {
+    /// Converts the hashed `Password` into `Bytes`, without copying its value.
+    fn from(value: Password<Hashed>) -> Self {
+        bytes::Bytes::from(value.1.into_string())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl TryFrom<bytes::Bytes> for Password<Hashed> {
+    type Error = argon2::password_hash::Error;
+
+    /// Parses a PHC string stored as `Bytes` into a hashed `Password`, caching its algorithm.
+    ///
+    /// The bytes are reused without copying when the `Bytes` is not shared. Values that are
+    /// not valid UTF-8 are rejected as `password_hash::Error::PhcStringField`.
+    fn try_from(value: bytes::Bytes) -> Result<Self, Self::Error> {
+        let value = String::from_utf8(Vec::from(value))
+            .map_err(|_| argon2::password_hash::Error::PhcStringField)?;
+        Password::from_phc(value)
+    }
+}
+
+impl TryFrom<OsString> for Password<Plain> {
+    type Error = InvalidUtf8Error;
+
+    /// Converts an `OsString`, such as an environment variable, into a plain text `Password`.
+    ///
+    /// Values that are not valid UTF-8 are rejected without being included in the error.
+    fn try_from(value: OsString) -> Result<Self, Self::Error> {
+        value
+            .into_string()
+            .map(Password::new)
+            .map_err(|_| InvalidUtf8Error)
+    }
+}
+
+impl TryFrom<&[u8]> for Password<Plain> {
+    type Error = InvalidUtf8Error;
+
+    /// Converts a byte slice, such as a binary secret read from a file, into a plain text
+    /// `Password`.
+    ///
+    /// Passwords are stored as strings, so values that are not valid UTF-8 are rejected
+    /// without being included in the error, rather than being altered.
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        std::str::from_utf8(value)
+            .map(Password::new)
+            .map_err(|_| InvalidUtf8Error)
+    }
+}
+
+impl TryFrom<Vec<u8>> for Password<Plain> {
+    type Error = InvalidUtf8Error;
+
+    /// Converts a byte vector into a plain text `Password`, reusing its buffer.
+    ///
+    /// Values that are not valid UTF-8 are rejected without being included in the error.
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        String::from_utf8(value)
+            .map(Password::new)
+            .map_err(|_| InvalidUtf8Error)
+    }
+}
+
+impl<T: sealed::Marker + ?Sized> Clone for Password<T> {
+    /// Clones the `Password`, locking the buffer of the clone separately.
+    fn clone(&self) -> Self {
+        Password::with_algorithm(self.1.clone(), self.2)
+    }
+}
+
+impl<T: ?Sized> PartialEq for Password<T> {
+    /// Compares the `Password` values, ignoring any cached metadata.
+    fn eq(&self, other: &Self) -> bool {
+        *self.1 == *other.1
+    }
+}
+
+impl<T: ?Sized> Eq for Password<T> {}
+
+impl From<String> for Password<Plain> {
+    /// Converts the `String` instance to a plain text `Password`.
+    fn from(value: String) -> Self {
+        Password::new(value)
+    }
+}
+
+/// Dereferences a hashed `Password` to its PHC string.
+///
+/// Plain text passwords intentionally do not implement `Deref`, so their value cannot be
+/// exposed through an implicit dereference:
+///
+/// ```compile_fail
+/// use tag_password::{Password, Plain};
+///
+/// let plain_password: Password<Plain> = Password::new("my_password");
+/// let _: &str = &plain_password;
+/// ```
+impl Deref for Password<Hashed> {
+    type Target = str;
+
+    /// Returns the PHC string of the hashed password.
+    fn deref(&self) -> &Self::Target {
+        &self.1
+    }
+}
+
+/// Placeholder written in place of a password value when formatting.
+const REDACTED: &str = "<redacted>";
+
+impl<T: ?Sized> Debug for Password<T> {
+    /// Formats the `Password` for debugging purposes, without revealing its value.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Password({REDACTED})")
+    }
+}
+
+impl<T: ?Sized> Display for Password<T> {
+    /// Formats the `Password` for displaying purposes, without revealing its value.
+    ///
+    /// Use `raw` to explicitly access the inner value.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{REDACTED}")
+    }
+}
+
+/// Hash of `"Password"` produced with the `Argon2Profile::Testing` configuration, shared by the
+/// test modules.
+#[cfg(test)]
+pub(crate) const TEST_HASH: &str = "$argon2id$v=19$m=8,t=1,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$VSGjAZoWLd8Ds9tkbDEx49KABqrdxsMoISbYU31PMbM";
+
+#[cfg(all(test, feature = "os-rng"))]
+mod tests {
+    use argon2::{
+        password_hash::{Salt, SaltString},
+        PasswordHash,
+    };
+    use proptest::prelude::*;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_core::OsRng;
+
+    use crate::{
+        enabled_algorithms, generate_salt_with, is_hashed_format, verify_str, Argon2Profile,
+        HashAlgorithm, HashError, Hashed, Password, Plain, VerifyError, MAX_HASH_INPUT_LEN,
+        TEST_HASH,
+    };
+
+    #[test]
+    fn argon_encoding_decoding() {
+        let plain_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = plain_password
+            .hash(None, &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        argon_encoded_password
+            .verify(None, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    fn argon_checked_roundtrip() {
+        let plain_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = plain_password
+            .hash_checked_roundtrip(None, &salt)
+            .expect("Argon2 checked roundtrip should not fail.");
+
+        argon_encoded_password
+            .verify(None, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    fn enabled_algorithms_reflect_features() {
+        assert!(enabled_algorithms().contains(&"argon2"));
+        assert_eq!(
+            enabled_algorithms().contains(&"bcrypt"),
+            cfg!(feature = "bcrypt")
+        );
+    }
+
+    #[test]
+    fn hash_errors_are_typed() {
+        let argon2 = Argon2Profile::Testing.argon2();
+        let salt = SaltString::generate(&mut OsRng);
+
+        assert_eq!(
+            Password::<Plain>::new("").hash(Some(argon2.clone()), &salt),
+            Err(HashError::EmptyInput)
+        );
+        #[cfg(not(feature = "prehash-long"))]
+        assert_eq!(
+            Password::<Plain>::new("a".repeat(MAX_HASH_INPUT_LEN + 1))
+                .hash(Some(argon2.clone()), &salt),
+            Err(HashError::TooLong {
+                max: MAX_HASH_INPUT_LEN
+            })
+        );
+        assert!(Password::<Plain>::new("a".repeat(MAX_HASH_INPUT_LEN))
+            .hash(Some(argon2.clone()), &salt)
+            .is_ok());
+        assert!(matches!(
+            Password::<Plain>::new("Password").hash(
+                Some(argon2),
+                Salt::from_b64("c2FsdA").expect("Salt should be valid.")
+            ),
+            Err(HashError::InvalidParams(_))
+        ));
+    }
+
+    #[test]
+    fn hashed_deref_yields_phc_string() {
+        let plain_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = plain_password
+            .hash(None, &salt)
+            .expect("Argon2 encoding should not fail.");
+        let phc: String = argon_encoded_password.clone().into();
+
+        assert_eq!(&*argon_encoded_password, phc);
+        assert!(argon_encoded_password.starts_with("$argon2"));
+    }
+
+    #[test]
+    fn password_into_cow() {
+        let plain: std::borrow::Cow<'static, str> = Password::<Plain>::new("Password").into();
+        let hashed: std::borrow::Cow<'static, str> =
+            Password::<Hashed>::new_unchecked(TEST_HASH).into();
+
+        assert!(matches!(plain, std::borrow::Cow::Owned(_)));
+        assert_eq!(plain, "Password");
+        assert_eq!(hashed, TEST_HASH);
+    }
+
+    #[test]
+    fn hashed_deref_binds_as_str() {
+        fn bind(value: &str) -> usize {
+            value.len()
+        }
+
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        assert_eq!(bind(&hashed_password), hashed_password.as_bytes().len());
+    }
+
+    #[test]
+    fn hash_records_salt() {
+        let plain_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let result = plain_password
+            .hash_with_salt_record(None, &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(result.salt, salt);
+        assert!(result.hash.contains(salt.as_str()));
+    }
+
+    #[test]
+    fn try_verify_distinguishes_mismatch_from_errors() {
+        let plain_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = plain_password
+            .hash(None, &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(
+            argon_encoded_password.try_verify(None, plain_password),
+            Ok(true)
+        );
+        assert_eq!(
+            argon_encoded_password.try_verify(None, Password::new("Wrong")),
+            Ok(false)
+        );
+
+        let malformed: Password<Hashed> = Password::new_unchecked("not a hash");
+        assert!(matches!(
+            malformed.try_verify(None, Password::new("Password")),
+            Err(VerifyError::InvalidHash(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "prehash-long"))]
+    fn verification_rejects_too_long_input() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let too_long = "a".repeat(MAX_HASH_INPUT_LEN + 1);
+
+        assert_eq!(
+            hashed_password.try_verify(None, too_long.clone()),
+            Err(VerifyError::TooLong {
+                max: MAX_HASH_INPUT_LEN
+            })
+        );
+        assert_eq!(
+            verify_str(TEST_HASH, &too_long, None),
+            Err(VerifyError::TooLong {
+                max: MAX_HASH_INPUT_LEN
+            })
+        );
+        assert_eq!(
+            hashed_password.verify(None, too_long),
+            Err(argon2::password_hash::Error::Password)
+        );
+    }
+
+    #[test]
+    fn is_same_as_detects_reuse() {
+        let current_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = current_password
+            .hash(None, &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(
+            argon_encoded_password.is_same_as(None, &current_password),
+            Ok(true)
+        );
+        assert_eq!(
+            argon_encoded_password.is_same_as(None, &Password::new("NewPassword")),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn hash_on_deserialize_hashes_plain_field() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::hash_on_deserialize")]
+            admin_password: Password<Hashed>,
+        }
+
+        let config: Config = serde_json::from_str(r#"{ "admin_password": "Password" }"#)
+            .expect("Deserialization should not fail.");
+
+        assert!(config.admin_password.starts_with("$argon2"));
+        config
+            .admin_password
+            .verify(None, Password::new("Password"))
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn hash_on_deserialize_uses_configured_argon2() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::hash_on_deserialize")]
+            admin_password: Password<Hashed>,
+        }
+
+        let config: Config =
+            crate::hash_on_deserialize::with_argon2(Argon2Profile::Testing.argon2(), || {
+                serde_json::from_str(r#"{ "admin_password": "Password" }"#)
+            })
+            .expect("Deserialization should not fail.");
+
+        assert!(config
+            .admin_password
+            .starts_with("$argon2id$v=19$m=8,t=1,p=1$"));
+        assert!(config.admin_password.matches(&Password::new("Password")));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn skip_password_omits_field_unless_exposed() {
+        #[derive(serde::Serialize)]
+        struct User {
+            name: &'static str,
+            #[serde(skip_serializing_if = "crate::skip_password::should_skip")]
+            password: Password<Hashed>,
+        }
+
+        let user = User {
+            name: "admin",
+            password: Password::new_unchecked(TEST_HASH),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&user).expect("Serialization should not fail."),
+            r#"{"name":"admin"}"#
+        );
+        assert_eq!(
+            crate::skip_password::exposed(|| serde_json::to_string(&user))
+                .expect("Serialization should not fail."),
+            format!(r#"{{"name":"admin","password":"{TEST_HASH}"}}"#)
+        );
+        assert!(!crate::skip_password::should_serialize());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip_as_string() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        let json = serde_json::to_string(&hashed_password).expect("Serialization should not fail.");
+        let deserialized: Password<Hashed> =
+            serde_json::from_str(&json).expect("Deserialization should not fail.");
+
+        assert_eq!(json, format!(r#""{TEST_HASH}""#));
+        assert_eq!(deserialized, hashed_password);
+    }
+
+    #[test]
+    fn raw_exposes_value_while_formatting_redacts() {
+        let plain_password: Password<Plain> = Password::new("hunter2");
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        assert_eq!(plain_password.raw(), "hunter2");
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+
+        assert!(!plain_password.to_string().contains("hunter2"));
+        assert!(!format!("{plain_password:?}").contains("hunter2"));
+        assert!(!hashed_password.to_string().contains("argon2"));
+        assert!(!format!("{hashed_password:?}").contains("argon2"));
+    }
+
+    #[test]
+    fn argon2id_shortcut() {
+        let plain_password = Password::new("Password");
+        let argon_encoded_password = plain_password
+            .hash_argon2id(&SaltString::generate(&mut OsRng))
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(
+            argon_encoded_password.algorithm(),
+            Some(HashAlgorithm::Argon2id)
+        );
+        argon_encoded_password
+            .verify(None, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    fn argon2i_shortcut() {
+        let plain_password = Password::new("Password");
+        let argon_encoded_password = plain_password
+            .hash_argon2i(&SaltString::generate(&mut OsRng))
+            .expect("Argon2 encoding should not fail.");
+
+        assert!(argon_encoded_password.starts_with("$argon2i$"));
+        assert_eq!(
+            argon_encoded_password.algorithm(),
+            Some(HashAlgorithm::Argon2i)
+        );
+        argon_encoded_password
+            .verify(None, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    fn argon2d_shortcut() {
+        let plain_password = Password::new("Password");
+        let argon_encoded_password = plain_password
+            .hash_argon2d(&SaltString::generate(&mut OsRng))
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(
+            argon_encoded_password.algorithm(),
+            Some(HashAlgorithm::Argon2d)
+        );
+        argon_encoded_password
+            .verify(None, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    fn prehashed_client_input_roundtrip() {
+        let client_hash = "e7cf3ef4f17c3999a94f2c6f612e8a888e5b1026878e4e19398b23bd38ec221a";
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = Password::from_prehashed_hex(client_hash)
+            .hash(Some(Argon2Profile::Testing.argon2()), &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        argon_encoded_password
+            .verify(
+                None,
+                Password::from_prehashed_hex(&client_hash.to_uppercase()),
+            )
+            .expect("Argon2 encoded password verification should not fail.");
+        assert!(argon_encoded_password
+            .verify(None, Password::new("Password"))
+            .is_err());
+    }
+
+    #[test]
+    fn matches_plain_password() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        assert!(hashed_password.matches(&Password::new("Password")));
+        assert!(!hashed_password.matches(&Password::new("Wrong")));
+        assert!(
+            !Password::<Hashed>::new_unchecked("not a hash").matches(&Password::new("Password"))
+        );
+    }
+
+    #[test]
+    fn verify_against_reuses_parsed_hash() {
+        let parsed = PasswordHash::new(TEST_HASH).expect("Parsing should not fail.");
+        let candidates =
+            ["password", "Password1", "Password", "hunter2"].map(Password::<Plain>::new);
+
+        let matching: Vec<_> = candidates
+            .iter()
+            .filter(|candidate| candidate.verify_against(None, &parsed).is_ok())
+            .map(Password::raw)
+            .collect();
+
+        assert_eq!(matching, ["Password"]);
+        assert_eq!(
+            candidates[0].verify_against(None, &parsed),
+            Err(argon2::password_hash::Error::Password)
+        );
+    }
+
+    #[test]
+    fn could_match_requires_identical_hashes() {
+        let first = Password::<Plain>::new("Password")
+            .hash(
+                Some(Argon2Profile::Testing.argon2()),
+                &SaltString::encode_b64(b"first_salt").expect("Salt should be valid."),
+            )
+            .expect("Argon2 encoding should not fail.");
+        let second = Password::<Plain>::new("Password")
+            .hash(
+                Some(Argon2Profile::Testing.argon2()),
+                &SaltString::encode_b64(b"second_salt").expect("Salt should be valid."),
+            )
+            .expect("Argon2 encoding should not fail.");
+
+        assert!(first.could_match(&first.clone()));
+        assert!(Password::<Hashed>::new_unchecked(TEST_HASH)
+            .could_match(&Password::new_unchecked(TEST_HASH)));
+        assert!(!first.could_match(&second));
+        assert!(first.matches(&Password::new("Password")));
+        assert!(second.matches(&Password::new("Password")));
+    }
+
+    #[test]
+    fn verify_returning_hands_back_plain_password() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        assert_eq!(
+            hashed_password
+                .verify_returning(None, Password::new("Password"))
+                .map(|plain| plain.raw().to_owned()),
+            Ok("Password".to_owned())
+        );
+        assert!(matches!(
+            hashed_password.verify_returning(None, Password::new("Wrong")),
+            Err((plain, VerifyError::Mismatch)) if plain.raw() == "Wrong"
+        ));
+        assert!(matches!(
+            Password::<Hashed>::new_unchecked("not a hash").verify_returning(None, Password::new("Password")),
+            Err((plain, VerifyError::InvalidHash(_))) if plain.raw() == "Password"
+        ));
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn hashed_password_bytes_roundtrip() {
+        let bytes = bytes::Bytes::from(Password::<Hashed>::new_unchecked(TEST_HASH));
+        let hashed_password =
+            Password::<Hashed>::try_from(bytes.clone()).expect("Parsing should not fail.");
+
+        assert_eq!(&bytes[..], TEST_HASH.as_bytes());
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+        assert_eq!(hashed_password.algorithm(), Some(HashAlgorithm::Argon2id));
+        assert!(Password::<Hashed>::try_from(bytes::Bytes::from_static(b"not a hash")).is_err());
+        assert!(Password::<Hashed>::try_from(bytes::Bytes::from_static(b"\xff\xfe")).is_err());
+    }
+
+    #[test]
+    fn parse_trims_stored_hash_whitespace() {
+        let hashed_password: Password<Hashed> = format!("{TEST_HASH}\n")
+            .parse()
+            .expect("Parsing should not fail.");
+        let padded = Password::<Hashed>::try_from(format!("  {TEST_HASH} \r\n").as_str())
+            .expect("Parsing should not fail.");
+
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+        assert_eq!(padded.raw(), TEST_HASH);
+        assert!(hashed_password
+            .verify(None, Password::new("Password"))
+            .is_ok());
+    }
+
+    #[test]
+    fn confirmation_matches_password() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        assert!(plain_password.confirm(&Password::new("Password")));
+        assert!(!plain_password.confirm(&Password::new("password")));
+        assert!(!plain_password.confirm(&Password::new("Password1")));
+        assert!(!plain_password.confirm(&Password::new("")));
+    }
+
+    #[test]
+    fn verify_variants_matches_any_variant() {
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let hashed_password = Password::<Plain>::new("Cafe\u{301}")
+            .hash(Some(Argon2Profile::Testing.argon2()), &salt)
+            .expect("Argon2 encoding should not fail.");
+        let variants = [
+            Password::new(" Caf\u{E9} "),
+            Password::new("Caf\u{E9}"),
+            Password::new("Cafe\u{301}"),
+        ];
+
+        assert_eq!(hashed_password.verify_variants(None, &variants), Ok(true));
+        assert_eq!(
+            hashed_password.verify_variants(None, &variants[..2]),
+            Ok(false)
+        );
+        assert_eq!(hashed_password.verify_variants(None, &[]), Ok(false));
+        assert!(Password::<Hashed>::new_unchecked("not a hash")
+            .verify_variants(None, &variants)
+            .is_err());
+    }
+
+    #[test]
+    fn fragments_join_into_passphrase() {
+        let fragments = ["correct", "horse", "battery", "staple"];
+
+        assert_eq!(
+            Password::<Plain>::from_fragments(&fragments, " ").raw(),
+            "correct horse battery staple"
+        );
+        assert_eq!(
+            Password::<Plain>::from_fragments(&fragments, "-"),
+            Password::new("correct-horse-battery-staple")
+        );
+    }
+
+    #[test]
+    fn single_line_accepts_clean_line() {
+        let plain_password =
+            Password::<Plain>::new_single_line("Pass word").expect("Clean lines should be valid.");
+
+        assert_eq!(plain_password.raw(), "Pass word");
+    }
+
+    #[test]
+    fn single_line_rejects_line_breaks() {
+        assert_eq!(
+            Password::<Plain>::new_single_line("Pass\nword"),
+            Err(crate::MultilineError)
+        );
+        assert_eq!(
+            Password::<Plain>::new_single_line("Password\r"),
+            Err(crate::MultilineError)
+        );
+    }
+
+    #[test]
+    fn hash_proof_converts_into_hashed() {
+        let plain_password: Password<Plain> = Password::new("Password");
+        let proof = plain_password
+            .hash_proof(
+                Some(Argon2Profile::Testing.argon2()),
+                &SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid."),
+            )
+            .expect("Argon2 encoding should not fail.");
+
+        let hashed_password = plain_password.into_hashed(proof);
+
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+        assert!(hashed_password.matches(&Password::new("Password")));
+    }
+
+    #[test]
+    #[ignore = "attempts to allocate 4 TiB, which may succeed with memory overcommit enabled"]
+    fn oversized_memory_cost_fails_allocation() {
+        let argon2 = argon2::Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            argon2::Params::new(u32::MAX, 1, 1, None).expect("Argon2 parameters should be valid."),
+        );
+
+        assert_eq!(
+            Password::<Plain>::new("Password").hash(Some(argon2), &crate::generate_salt()),
+            Err(HashError::AllocationFailed {
+                memory_kib: u32::MAX
+            })
+        );
+    }
+
+    #[test]
+    #[ignore = "attempts to allocate 4 TiB, which may succeed with memory overcommit enabled"]
+    fn oversized_stored_memory_cost_fails_allocation() {
+        let hash = TEST_HASH.replace("m=8", &format!("m={}", u32::MAX));
+
+        assert_eq!(
+            verify_str(&hash, "Password", None),
+            Err(VerifyError::AllocationFailed {
+                memory_kib: u32::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn verify_stored_hash_string() {
+        assert_eq!(verify_str(TEST_HASH, "Password", None), Ok(()));
+        assert_eq!(
+            verify_str(TEST_HASH, "Wrong", None),
+            Err(VerifyError::Mismatch)
+        );
+        assert!(matches!(
+            verify_str("not a hash", "Password", None),
+            Err(VerifyError::InvalidHash(_))
+        ));
+    }
+
+    #[test]
+    fn shared_argon2_configuration() {
+        let argon2 = std::sync::Arc::new(Argon2Profile::Testing.argon2());
+        let plain_password = Password::new("Password");
+        let salt = SaltString::generate(&mut OsRng);
+
+        let argon_encoded_password = plain_password
+            .hash_shared(&argon2, &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        argon_encoded_password
+            .verify_shared(&argon2, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+        assert!(argon_encoded_password
+            .verify_shared(&argon2, Password::new("Wrong"))
+            .is_err());
+    }
+
+    #[test]
+    fn algorithm_cached_on_parse() {
+        let parsed: Password<Hashed> = TEST_HASH.parse().expect("Parsing should not fail.");
+        let converted =
+            Password::<Hashed>::try_from(TEST_HASH).expect("Conversion should not fail.");
+
+        assert_eq!(parsed.2, Some(HashAlgorithm::Argon2id));
+        assert_eq!(converted.2, Some(HashAlgorithm::Argon2id));
+        assert_eq!(parsed.algorithm(), HashAlgorithm::detect(TEST_HASH));
+        assert_eq!(parsed, Password::<Hashed>::new_unchecked(TEST_HASH));
+        assert!("not a hash".parse::<Password<Hashed>>().is_err());
+    }
+
+    #[test]
+    fn from_phc_validates_stored_hashes() {
+        let hashed_password =
+            Password::<Hashed>::from_phc(TEST_HASH).expect("Parsing should not fail.");
+
+        assert_eq!(hashed_password.2, Some(HashAlgorithm::Argon2id));
+        assert!(hashed_password.matches(&Password::new("Password")));
+        assert!(Password::<Hashed>::from_phc("not a hash").is_err());
+        assert!(Password::<Hashed>::from_phc("$argon2id").is_err());
+        assert!(Password::<Hashed>::from_phc("$argon2id$v=19$m=8,t=1,p=1").is_err());
+        assert!(format!(" {TEST_HASH}\n")
+            .parse::<Password<Hashed>>()
+            .is_ok());
+    }
+
+    #[test]
+    fn algorithm_detection() {
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(TEST_HASH).algorithm(),
+            Some(HashAlgorithm::Argon2id)
+        );
+        assert_eq!(
+            HashAlgorithm::detect("$argon2i$v=19$m=8,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g"),
+            Some(HashAlgorithm::Argon2i)
+        );
+        assert_eq!(
+            HashAlgorithm::detect("$2y$10$abcdefghijklmnopqrstuv"),
+            Some(HashAlgorithm::Bcrypt)
+        );
+        assert_eq!(
+            HashAlgorithm::detect("$scrypt$ln=16,r=8,p=1$c2FsdA$aGFzaA"),
+            Some(HashAlgorithm::Scrypt)
+        );
+        assert_eq!(HashAlgorithm::detect("Password"), None);
+    }
+
+    #[test]
+    fn verify_min_duration_pads_fast_failures() {
+        let min = std::time::Duration::from_millis(100);
+        let started = std::time::Instant::now();
+
+        assert!(Password::<Hashed>::new_unchecked("not a hash")
+            .verify_min_duration(None, "Password".to_owned(), min)
+            .is_err());
+        assert!(started.elapsed() >= min);
+
+        let started = std::time::Instant::now();
+        assert!(Password::<Hashed>::new_unchecked(TEST_HASH)
+            .verify_min_duration(None, "Password".to_owned(), min)
+            .is_ok());
+        assert!(started.elapsed() >= min);
+    }
+
+    #[test]
+    fn algorithm_predicates() {
+        let argon2: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let argon2i: Password<Hashed> =
+            Password::new_unchecked("$argon2i$v=19$m=8,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g");
+        let bcrypt: Password<Hashed> = Password::new_unchecked("$2y$10$abcdefghijklmnopqrstuv");
+        let scrypt: Password<Hashed> =
+            Password::new_unchecked("$scrypt$ln=16,r=8,p=1$c2FsdA$aGFzaA");
+        let unknown: Password<Hashed> = Password::new_unchecked("Password");
+
+        assert!(argon2.is_argon2() && !argon2.is_bcrypt() && !argon2.is_scrypt());
+        assert!(argon2i.is_argon2());
+        assert!(bcrypt.is_bcrypt() && !bcrypt.is_argon2() && !bcrypt.is_scrypt());
+        assert!(scrypt.is_scrypt() && !scrypt.is_argon2() && !scrypt.is_bcrypt());
+        assert!(!unknown.is_argon2() && !unknown.is_bcrypt() && !unknown.is_scrypt());
+    }
+
+    #[test]
+    fn hashed_format_detection() {
+        assert!(is_hashed_format(TEST_HASH));
+        assert!(!is_hashed_format("Password"));
+        assert!(!is_hashed_format(""));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn hash_never_equals_plaintext(plain in ".{1,64}") {
+            let salt = SaltString::generate(&mut OsRng);
+            let argon_encoded_password = Password::<Plain>::new(plain.clone())
+                .hash(Some(Argon2Profile::Testing.argon2()), &salt)
+                .expect("Argon2 encoding should not fail.");
+
+            prop_assert_ne!(argon_encoded_password.as_bytes(), plain.as_bytes());
+            prop_assert!(argon_encoded_password.starts_with("$argon2"));
+            prop_assert!(is_hashed_format(&argon_encoded_password));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_hashed_requires_phc_string() {
+        let hashed_password: Password<Hashed> = serde_json::from_str(&format!(r#""{TEST_HASH}""#))
+            .expect("Deserialization should not fail.");
+
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+        assert!(serde_json::from_str::<Password<Hashed>>(r#""Password""#).is_err());
+        assert!(serde_json::from_str::<Password<Hashed>>(r#""""#).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_hashed_human_readable_and_compact() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        let json = serde_json::to_string(&hashed_password).expect("Serialization should not fail.");
+        let binary = bincode::serialize(&hashed_password).expect("Serialization should not fail.");
+
+        assert_eq!(json, format!(r#""{TEST_HASH}""#));
+        assert!(binary
+            .windows(b"tag_password_salt".len())
+            .any(|window| window == b"tag_password_salt"));
+        assert!(!binary
+            .windows(b"dGFnX3Bhc3N3b3JkX3NhbHQ".len())
+            .any(|window| window == b"dGFnX3Bhc3N3b3JkX3NhbHQ"));
+        assert_eq!(
+            serde_json::from_str::<Password<Hashed>>(&json)
+                .expect("Deserialization should not fail."),
+            hashed_password
+        );
+        assert_eq!(
+            bincode::deserialize::<Password<Hashed>>(&binary)
+                .expect("Deserialization should not fail."),
+            hashed_password
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn bincode_rejects_truncated_hashes() {
+        for value in ["$argon2id", "$argon2id$v=19$m=8,t=1,p=1"] {
+            let compact =
+                super::compact::CompactHash::from_phc(value).expect("Parsing should not fail.");
+            let binary = bincode::serialize(&compact).expect("Serialization should not fail.");
+
+            assert!(bincode::deserialize::<Password<Hashed>>(&binary).is_err());
+        }
+
+        let binary = bincode::serialize(&Password::<Hashed>::new_unchecked(TEST_HASH))
+            .expect("Serialization should not fail.");
+        let parsed = bincode::deserialize::<Password<Hashed>>(&binary)
+            .expect("Deserialization should not fail.");
+        assert_eq!(parsed.2, Some(HashAlgorithm::Argon2id));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_rejects_malformed_phc_strings() {
+        for value in [r#""$hunter2""#, r#""$argon2id""#] {
+            assert!(serde_json::from_str::<Password<Hashed>>(value).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_accepts_unknown_parameters() {
+        let value = "$argon2id$v=19$m=8,t=1,p=1,future=on$dGFnX3Bhc3N3b3JkX3NhbHQ$VSGjAZoWLd8Ds9tkbDEx49KABqrdxsMoISbYU31PMbM";
+        let json = format!(r#""{value}""#);
+
+        let hashed_password: Password<Hashed> =
+            serde_json::from_str(&json).expect("Deserialization should not fail.");
+
+        assert_eq!(hashed_password.raw(), value);
+        assert_eq!(
+            serde_json::to_string(&hashed_password).expect("Serialization should not fail."),
+            json
+        );
+        assert!(hashed_password
+            .verify(None, Password::new("Password"))
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_rejects_corrupt_hash() {
+        let corrupt: Password<Hashed> =
+            Password::new_unchecked("$argon2id$v=19$m=8,t=1,p=1$not base64!");
+
+        assert_eq!(
+            serde_json::to_string(&Password::<Hashed>::new_unchecked(TEST_HASH)).ok(),
+            Some(format!(r#""{TEST_HASH}""#))
+        );
+        assert!(serde_json::to_string(&corrupt).is_err());
+        assert!(serde_json::to_string(&Password::<Hashed>::new_unchecked("Password")).is_err());
+        assert!(bincode::serialize(&corrupt).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "trim-on-deserialize"))]
+    fn deserialize_trims_plain_password() {
+        let plain_password: Password<Plain> =
+            serde_json::from_str(r#"" Password \n""#).expect("Deserialization should not fail.");
+
+        assert_eq!(plain_password.raw(), "Password");
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", not(feature = "trim-on-deserialize")))]
+    fn deserialize_keeps_plain_password_whitespace() {
+        let plain_password: Password<Plain> =
+            serde_json::from_str(r#"" Password \n""#).expect("Deserialization should not fail.");
+
+        assert_eq!(plain_password.raw(), " Password \n");
+    }
+
+    #[test]
+    fn plain_password_from_os_string() {
+        let plain_password = Password::<Plain>::try_from(std::ffi::OsString::from("Password"))
+            .expect("Conversion should not fail.");
+
+        assert_eq!(plain_password.raw(), "Password");
+    }
+
+    #[test]
+    fn plain_password_from_bytes() {
+        let bytes = "Pass w\u{F6}rd".as_bytes();
+
+        let from_slice = Password::<Plain>::try_from(bytes).expect("Bytes should be valid UTF-8.");
+        let from_vec =
+            Password::<Plain>::try_from(bytes.to_vec()).expect("Bytes should be valid UTF-8.");
+
+        assert_eq!(from_slice.as_bytes(), bytes);
+        assert_eq!(from_vec.as_bytes(), bytes);
+        assert_eq!(
+            Password::<Plain>::try_from(&[b'P', 0xff, b'w'][..]),
+            Err(crate::InvalidUtf8Error)
+        );
+        assert_eq!(
+            Password::<Plain>::try_from(vec![b'P', 0xff, b'w']),
+            Err(crate::InvalidUtf8Error)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn plain_password_from_invalid_os_string() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let value = std::ffi::OsString::from_vec(vec![b'P', 0xff, b'w']);
+
+        assert_eq!(
+            Password::<Plain>::try_from(value),
+            Err(crate::InvalidUtf8Error)
+        );
+    }
+
+    #[test]
+    fn log_value_never_contains_password() {
+        let plain_password: Password<Plain> = Password::new("hunter2");
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+
+        assert!(!plain_password.log_value().contains("hunter2"));
+        assert!(!hashed_password.log_value().contains(TEST_HASH));
+        assert_eq!(plain_password.log_value(), hashed_password.log_value());
+        assert_eq!(plain_password.log_value(), plain_password.to_string());
+    }
+
+    #[test]
+    fn salt_generation_with_deterministic_rng() {
+        let first = generate_salt_with(&mut ChaCha20Rng::seed_from_u64(0));
+        let second = generate_salt_with(&mut ChaCha20Rng::seed_from_u64(0));
+        let other = generate_salt_with(&mut ChaCha20Rng::seed_from_u64(42));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn password_generation_with_deterministic_rng() {
+        let first = Password::generate_with_rng(&mut ChaCha20Rng::seed_from_u64(0), 24);
+        let second = Password::generate_with_rng(&mut ChaCha20Rng::seed_from_u64(0), 24);
+        let other = Password::generate_with_rng(&mut ChaCha20Rng::seed_from_u64(42), 24);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(first.raw().chars().count(), 24);
+        assert!(first
+            .as_bytes()
+            .iter()
+            .all(|byte| crate::PASSWORD_CHARSET.contains(byte)));
+    }
+
+    #[test]
+    fn password_generation_with_os_rng() {
+        let first = Password::generate(24);
+        let second = Password::generate(24);
+
+        assert_eq!(first.as_bytes().len(), 24);
+        assert_ne!(first, second);
     }
 }
@@ -0,0 +1,119 @@
+//! Memory locking of plain text password buffers.
+//!
+//! With the `mlock` feature enabled on Unix platforms, the heap buffer of each
+//! `Password<Plain>` is locked with `mlock` when the password is created, so the operating
+//! system does not swap it to disk, and unlocked when the password is dropped. With the
+//! `zeroize` feature enabled as well, a locked buffer is cleared before it is unlocked and
+//! released.
+//!
+//! Locking is best effort. It fails silently when the process exceeds `RLIMIT_MEMLOCK`, and
+//! applies to whole memory pages, so dropping a password also unlocks any other buffer sharing
+//! its pages. Buffers moved out of a password, such as through `From<Password<T>> for String`,
+//! are unlocked first and not covered afterwards.
+
+use crate::sealed::Marker;
+use std::ops::{Deref, DerefMut};
+
+/// Value of a password, with its heap buffer locked in memory while it is owned.
+///
+/// Owning the buffer guarantees it is unlocked before it is released. The buffer is only
+/// locked for plain text passwords when the `mlock` feature is enabled on a Unix platform.
+pub(crate) struct LockedString {
+    value: String,
+    #[cfg(all(feature = "mlock", unix))]
+    region: Option<(usize, usize)>,
+}
+
+impl LockedString {
+    /// Takes ownership of the value of a password with the marker `T`, locking only plain
+    /// text passwords.
+    #[cfg_attr(
+        not(all(feature = "mlock", unix)),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    pub(crate) fn new<T: Marker + ?Sized>(value: String) -> Self {
+        LockedString {
+            #[cfg(all(feature = "mlock", unix))]
+            region: (T::PLAIN && !value.is_empty())
+                .then_some((value.as_ptr() as usize, value.len()))
+                .filter(|&(address, len)| {
+                    // SAFETY: The range covers the initialized bytes of a live allocation, and
+                    // `mlock` only changes how its pages are paged, not their contents.
+                    unsafe { libc::mlock(address as *const libc::c_void, len) == 0 }
+                }),
+            value,
+        }
+    }
+
+    /// Unlocks the buffer and returns the value.
+    pub(crate) fn into_string(mut self) -> String {
+        self.unlock();
+        std::mem::take(&mut self.value)
+    }
+
+    /// Unlocks the pages of the buffer, if they are locked.
+    fn unlock(&mut self) {
+        #[cfg(all(feature = "mlock", unix))]
+        if let Some((address, len)) = self.region.take() {
+            // SAFETY: `munlock` only changes how the pages are paged and does not access them,
+            // and the buffer is still alive.
+            unsafe {
+                libc::munlock(address as *const libc::c_void, len);
+            }
+        }
+    }
+
+    /// Returns `true` if the buffer is currently locked.
+    #[cfg(all(test, feature = "mlock", unix))]
+    pub(crate) fn is_locked(&self) -> bool {
+        self.region.is_some()
+    }
+}
+
+impl Deref for LockedString {
+    type Target = String;
+
+    /// Returns the value of the password.
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for LockedString {
+    /// Returns the value of the password for in-place changes that keep its allocation, such
+    /// as zeroizing it.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl Drop for LockedString {
+    /// Clears the buffer if it is locked and the `zeroize` feature is enabled, then unlocks it
+    /// before it is released.
+    fn drop(&mut self) {
+        #[cfg(all(feature = "mlock", unix, feature = "zeroize"))]
+        if self.region.is_some() {
+            zeroize::Zeroize::zeroize(&mut self.value);
+        }
+        self.unlock();
+    }
+}
+
+#[cfg(all(test, feature = "mlock", unix))]
+mod tests {
+    use crate::{Hashed, Password, Plain};
+
+    #[test]
+    fn locked_passwords_keep_their_value() {
+        let plain_password: Password<Plain> = Password::new("Password");
+        let cloned_password = plain_password.clone();
+
+        assert_eq!(plain_password.as_bytes(), b"Password");
+        assert_eq!(cloned_password.as_bytes(), b"Password");
+        assert_eq!(plain_password, cloned_password);
+        assert!(plain_password.1.is_locked());
+        assert!(cloned_password.1.is_locked());
+        assert!(!Password::<Hashed>::new_unchecked("Password").1.is_locked());
+        assert_eq!(String::from(plain_password), "Password");
+    }
+}
@@ -0,0 +1,177 @@
+//! Verification integrated with failed attempt counters for account lockout.
+
+use crate::{Hashed, LockedOrMismatch, Password, Plain};
+use argon2::Argon2;
+
+/// Counter of failed verification attempts, typically one per account.
+///
+/// Implement this trait over a shared store, such as a database or cache, to enforce lockouts
+/// across several servers, making `record_failure` a single atomic update of the store, such
+/// as an increment returning the new count. `InMemoryTracker` is a simple implementation for a
+/// single process.
+pub trait AttemptTracker {
+    /// Retrieves the number of failed attempts still allowed before the account is locked.
+    fn remaining_attempts(&self) -> u32;
+
+    /// Records a failed verification attempt.
+    ///
+    /// Returns the number of failed attempts still allowed after this one, read in the same
+    /// atomic update that records it, so concurrent failures each observe a distinct count.
+    fn record_failure(&mut self) -> u32;
+
+    /// Resets the counter after a successful verification.
+    fn reset(&mut self);
+}
+
+/// `AttemptTracker` locking after a fixed number of failed attempts, kept in memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InMemoryTracker {
+    max_attempts: u32,
+    failures: u32,
+}
+
+impl InMemoryTracker {
+    /// Creates a new `InMemoryTracker` allowing the provided number of failed attempts.
+    pub fn new(max_attempts: u32) -> Self {
+        InMemoryTracker {
+            max_attempts,
+            failures: 0,
+        }
+    }
+}
+
+impl AttemptTracker for InMemoryTracker {
+    fn remaining_attempts(&self) -> u32 {
+        self.max_attempts.saturating_sub(self.failures)
+    }
+
+    fn record_failure(&mut self) -> u32 {
+        self.failures = self.failures.saturating_add(1);
+        self.remaining_attempts()
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+impl Password<Hashed> {
+    /// Verifies the hashed password, recording the outcome in an attempt tracker.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    /// - `plain`: A plain text password used for verification.
+    /// - `tracker`: The failed attempt counter of the account.
+    ///
+    /// When the tracker has no attempts left, the password is not verified at all. A wrong
+    /// password records a failure and a match resets the tracker. Errors with the stored hash
+    /// or the backend are not counted as failures.
+    ///
+    /// The remaining count reported for a wrong password is the one returned by
+    /// `record_failure`, so it stays accurate under concurrent attempts. The check made before
+    /// verifying is a separate read, however: attempts racing while one attempt is left may all
+    /// be verified, so a shared store allows up to one extra guess per concurrent request.
+    ///
+    /// Returns `Ok(())` if the password matches. Otherwise, `LockedOrMismatch::Locked` is
+    /// returned if no attempts are left, `LockedOrMismatch::Mismatch` with the number of
+    /// remaining attempts for a wrong password, and `LockedOrMismatch::Verify` if the hash
+    /// cannot be verified.
+    pub fn verify_tracked(
+        &self,
+        argon2: Option<Argon2>,
+        plain: impl Into<Password<Plain>>,
+        tracker: &mut impl AttemptTracker,
+    ) -> Result<(), LockedOrMismatch> {
+        if tracker.remaining_attempts() == 0 {
+            return Err(LockedOrMismatch::Locked);
+        }
+
+        match self.try_verify(argon2, plain) {
+            Ok(true) => {
+                tracker.reset();
+                Ok(())
+            }
+            Ok(false) => Err(LockedOrMismatch::Mismatch {
+                remaining: tracker.record_failure(),
+            }),
+            Err(error) => Err(LockedOrMismatch::Verify(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttemptTracker, InMemoryTracker};
+    use crate::{Hashed, LockedOrMismatch, Password, TEST_HASH};
+
+    #[test]
+    fn repeated_failures_lock() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let mut tracker = InMemoryTracker::new(2);
+
+        assert_eq!(
+            hashed_password.verify_tracked(None, "Wrong".to_owned(), &mut tracker),
+            Err(LockedOrMismatch::Mismatch { remaining: 1 })
+        );
+        assert_eq!(
+            hashed_password.verify_tracked(None, "Wrong".to_owned(), &mut tracker),
+            Err(LockedOrMismatch::Mismatch { remaining: 0 })
+        );
+        assert_eq!(
+            hashed_password.verify_tracked(None, "Password".to_owned(), &mut tracker),
+            Err(LockedOrMismatch::Locked)
+        );
+        assert!(matches!(
+            Password::<Hashed>::new_unchecked("not a hash").verify_tracked(
+                None,
+                "Password".to_owned(),
+                &mut InMemoryTracker::new(1)
+            ),
+            Err(LockedOrMismatch::Verify(_))
+        ));
+    }
+
+    #[test]
+    fn success_resets_tracker() {
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let mut tracker = InMemoryTracker::new(3);
+
+        assert!(hashed_password
+            .verify_tracked(None, "Wrong".to_owned(), &mut tracker)
+            .is_err());
+        assert_eq!(tracker.remaining_attempts(), 2);
+        assert_eq!(
+            hashed_password.verify_tracked(None, "Password".to_owned(), &mut tracker),
+            Ok(())
+        );
+        assert_eq!(tracker.remaining_attempts(), 3);
+    }
+
+    #[test]
+    fn mismatch_reports_count_from_record_failure() {
+        /// Tracker over a shared store where another server records a failure concurrently.
+        struct RacingTracker(InMemoryTracker);
+
+        impl AttemptTracker for RacingTracker {
+            fn remaining_attempts(&self) -> u32 {
+                self.0.remaining_attempts()
+            }
+
+            fn record_failure(&mut self) -> u32 {
+                self.0.record_failure();
+                self.0.record_failure()
+            }
+
+            fn reset(&mut self) {
+                self.0.reset();
+            }
+        }
+
+        let hashed_password: Password<Hashed> = Password::new_unchecked(TEST_HASH);
+        let mut tracker = RacingTracker(InMemoryTracker::new(3));
+
+        assert_eq!(
+            hashed_password.verify_tracked(None, "Wrong".to_owned(), &mut tracker),
+            Err(LockedOrMismatch::Mismatch { remaining: 1 })
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Serde helpers distinguishing absent and empty optional password fields.
+//!
+//! Use this module with `#[serde(default, with = "tag_password::optional_password")]` on an
+//! `Option<Password<T>>` field of a PATCH-style request, where an absent field means the
+//! password is left unchanged. A missing or `null` field deserializes to `None`, an empty
+//! string is rejected and any other string deserializes to `Some`. The `default` attribute is
+//! required for a missing field to be accepted.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use tag_password::{Password, Plain};
+//!
+//! #[derive(Deserialize)]
+//! struct UpdateUser {
+//!     name: Option<String>,
+//!     #[serde(default, with = "tag_password::optional_password")]
+//!     password: Option<Password<Plain>>,
+//! }
+//!
+//! let update: UpdateUser = serde_json::from_str(r#"{"name":"admin"}"#).unwrap();
+//! assert!(update.password.is_none());
+//! assert!(serde_json::from_str::<UpdateUser>(r#"{"password":""}"#).is_err());
+//! ```
+
+use crate::Password;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Deserializes an optional password, rejecting empty strings.
+///
+/// Present values are deserialized as `Password<T>`, so they are validated and trimmed exactly
+/// as with a required field.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<Password<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ?Sized,
+    Password<T>: Deserialize<'de>,
+{
+    match Option::<Password<T>>::deserialize(deserializer)? {
+        Some(password) if password.as_bytes().is_empty() => {
+            Err(D::Error::custom("password must not be empty"))
+        }
+        password => Ok(password),
+    }
+}
+
+/// Serializes an optional password, writing a none value when it is absent.
+pub fn serialize<S, T>(value: &Option<Password<T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: ?Sized,
+    Password<T>: Serialize,
+{
+    value.serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Password, Plain};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    struct UpdateUser {
+        #[serde(default, with = "super")]
+        password: Option<Password<Plain>>,
+    }
+
+    #[test]
+    fn absent_password_is_none() {
+        let update: UpdateUser =
+            serde_json::from_str("{}").expect("Deserialization should not fail.");
+        let null: UpdateUser =
+            serde_json::from_str(r#"{"password":null}"#).expect("Deserialization should not fail.");
+
+        assert!(update.password.is_none());
+        assert!(null.password.is_none());
+        assert_eq!(
+            serde_json::to_string(&update).expect("Serialization should not fail."),
+            r#"{"password":null}"#
+        );
+    }
+
+    #[test]
+    fn empty_password_is_rejected() {
+        assert!(serde_json::from_str::<UpdateUser>(r#"{"password":""}"#).is_err());
+    }
+
+    #[test]
+    fn present_password_is_some() {
+        let update: UpdateUser = serde_json::from_str(r#"{"password":"Password"}"#)
+            .expect("Deserialization should not fail.");
+
+        assert_eq!(
+            update.password.as_ref().map(Password::raw),
+            Some("Password")
+        );
+        assert_eq!(
+            serde_json::to_string(&update).expect("Serialization should not fail."),
+            r#"{"password":"Password"}"#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "argon2")]
+    fn hashed_password_is_validated() {
+        use crate::Hashed;
+
+        #[derive(Deserialize)]
+        struct StoredUser {
+            #[serde(default, with = "super")]
+            password: Option<Password<Hashed>>,
+        }
+
+        assert!(serde_json::from_str::<StoredUser>(r#"{"password":"not a hash"}"#).is_err());
+        assert!(serde_json::from_str::<StoredUser>(r#"{"password":"$argon2id$v=19$m=8,t=1,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$VSGjAZoWLd8Ds9tkbDEx49KABqrdxsMoISbYU31PMbM"}"#)
+            .expect("Deserialization should not fail.")
+            .password
+            .is_some());
+    }
+}
@@ -0,0 +1,593 @@
+//! Inspection of the parameters embedded in hashed passwords.
+
+use crate::{verify_phc, HashAlgorithm, Hashed, Password, Plain, VerifyError};
+use argon2::{
+    password_hash::{Error, Output, Result},
+    Algorithm, Argon2, Params, PasswordHash, Version,
+};
+use std::collections::HashMap;
+
+/// Strength of a verified hash compared to a target Argon2 configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeakFlag {
+    /// The stored parameters meet or exceed the target configuration.
+    Strong,
+    /// The stored parameters are below the target configuration and the password should be
+    /// rehashed.
+    Weak,
+}
+
+/// Argon2 configuration new hashes are produced with, which stored hashes are compared against.
+///
+/// `Argon2` does not expose its algorithm and version, so they are given explicitly here.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashTarget {
+    /// The Argon2 variant.
+    pub algorithm: Algorithm,
+    /// The Argon2 version.
+    pub version: Version,
+    /// The Argon2 parameters.
+    pub params: Params,
+}
+
+impl HashTarget {
+    /// Creates a new `HashTarget` from the provided configuration.
+    ///
+    /// - `algorithm`: The Argon2 variant.
+    /// - `version`: The Argon2 version.
+    /// - `params`: The Argon2 parameters.
+    pub fn new(algorithm: Algorithm, version: Version, params: Params) -> Self {
+        HashTarget {
+            algorithm,
+            version,
+            params,
+        }
+    }
+
+    /// Builds the `Argon2` configuration of the target.
+    pub fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(self.algorithm, self.version, self.params.clone())
+    }
+}
+
+impl Default for HashTarget {
+    /// Creates the target matching `Argon2::default()`.
+    fn default() -> Self {
+        HashTarget::new(Algorithm::default(), Version::default(), Params::default())
+    }
+}
+
+/// Distribution of the work factors of a set of hashed passwords, produced by `analyze`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HashAudit {
+    /// Number of hashes per detected algorithm.
+    pub algorithms: HashMap<HashAlgorithm, usize>,
+    /// Number of hashes whose algorithm could not be identified.
+    pub unknown: usize,
+    /// Number of hashes of an identified algorithm other than Argon2, such as bcrypt, which
+    /// are left out of the work factor statistics.
+    pub non_argon2: usize,
+    /// Memory costs of the Argon2 hashes, in KiB, or `None` if there are no Argon2 hashes.
+    pub memory_kib: Option<WorkFactorStats>,
+    /// Iteration counts of the Argon2 hashes, or `None` if there are no Argon2 hashes.
+    pub iterations: Option<WorkFactorStats>,
+}
+
+/// Minimum, median and maximum of a work factor across hashed passwords.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorkFactorStats {
+    /// The lowest value.
+    pub min: u32,
+    /// The median value, the lower of the two middle values for an even number of hashes.
+    pub median: u32,
+    /// The highest value.
+    pub max: u32,
+}
+
+impl WorkFactorStats {
+    /// Computes the statistics of the values, or `None` if there are none.
+    fn from_values(mut values: Vec<u32>) -> Option<Self> {
+        values.sort_unstable();
+        Some(WorkFactorStats {
+            min: *values.first()?,
+            median: values[(values.len() - 1) / 2],
+            max: *values.last()?,
+        })
+    }
+}
+
+impl Password<Hashed> {
+    /// Parses the Argon2 parameters embedded in the hashed password.
+    ///
+    /// Produces a result containing the `Params` of the hash if successful. If the hash cannot
+    /// be parsed or is not an Argon2 hash, an `argon2::password_hash::Result` with an error is
+    /// returned.
+    pub fn params(&self) -> Result<Params> {
+        parse_argon2(&self.1).map(|(_, params)| params)
+    }
+
+    /// Summarizes the configuration of the hashed password on a single line.
+    ///
+    /// The summary has the form `"argon2id v19 m=19456 t=2 p=1"` and never includes the salt
+    /// or the digest, so it can be shown in logs and admin tools. Nothing is rehashed.
+    ///
+    /// Produces a result containing the summary if successful. If the hash cannot be parsed or
+    /// is not an Argon2 hash, an `argon2::password_hash::Result` with an error is returned.
+    pub fn describe(&self) -> Result<String> {
+        let (hash, params) = parse_argon2(&self.1)?;
+        Ok(format!(
+            "{} v{} m={} t={} p={}",
+            hash.algorithm,
+            version(&hash),
+            params.m_cost(),
+            params.t_cost(),
+            params.p_cost()
+        ))
+    }
+
+    /// Extracts the raw digest bytes from the hash portion of the PHC string.
+    ///
+    /// Produces a result containing the decoded digest if successful. If the hash cannot be
+    /// parsed or has no hash portion, an `argon2::password_hash::Result` with an error is
+    /// returned.
+    pub fn digest(&self) -> Result<Vec<u8>> {
+        Ok(hash_output(&self.1)?.as_bytes().to_vec())
+    }
+
+    /// Retrieves the length in bytes of the digest in the PHC string.
+    ///
+    /// Produces a result containing the digest length if successful. If the hash cannot be
+    /// parsed or has no hash portion, an `argon2::password_hash::Result` with an error is
+    /// returned.
+    pub fn output_len(&self) -> Result<usize> {
+        Ok(hash_output(&self.1)?.len())
+    }
+
+    /// Checks whether the hashed password should be rehashed with the target configuration.
+    ///
+    /// - `target`: The configuration new hashes are produced with.
+    ///
+    /// Returns `Ok(true)` if the algorithm, version, memory, iteration, parallelism or output
+    /// length parameters of the hash differ from the target, and `Ok(false)` otherwise. If the
+    /// hash cannot be parsed, an `argon2::password_hash::Result` with an error is returned.
+    pub fn needs_rehash(&self, target: &HashTarget) -> Result<bool> {
+        let hash = PasswordHash::new(&self.1)?;
+        if hash.algorithm != target.algorithm.ident() || version(&hash) != u32::from(target.version)
+        {
+            return Ok(true);
+        }
+        let params = Params::try_from(&hash)?;
+        let target = &target.params;
+        Ok(params.m_cost() != target.m_cost()
+            || params.t_cost() != target.t_cost()
+            || params.p_cost() != target.p_cost()
+            || output_len(&params) != output_len(target))
+    }
+
+    /// Checks whether the parameters of the hashed password meet minimum work factors, such as
+    /// the floor of a compliance policy.
+    ///
+    /// - `min_memory_kib`: The minimum memory cost, in KiB.
+    /// - `min_iterations`: The minimum number of iterations.
+    ///
+    /// Unlike `needs_rehash`, hashes above the minimums are accepted even if they differ from
+    /// the configuration new hashes are produced with.
+    ///
+    /// Returns `Ok(true)` if both the memory cost and the number of iterations are at least the
+    /// minimums, and `Ok(false)` otherwise. If the hash cannot be parsed or is not an Argon2
+    /// hash, an `argon2::password_hash::Result` with an error is returned.
+    pub fn meets_minimums(&self, min_memory_kib: u32, min_iterations: u32) -> Result<bool> {
+        let params = self.params()?;
+        Ok(params.m_cost() >= min_memory_kib && params.t_cost() >= min_iterations)
+    }
+
+    /// Verifies the hashed password and flags whether its parameters are below the target.
+    ///
+    /// - `target`: The configuration new hashes are produced with.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// This combines `verify` and a parameter check in a single call for login paths.
+    ///
+    /// Returns `WeakFlag::Weak` if the password matches and any of the memory, iteration or
+    /// parallelism parameters are below the target, and `WeakFlag::Strong` if it matches
+    /// otherwise. A wrong password is reported as `VerifyError::Mismatch`.
+    pub fn verify_flagging_weak(
+        &self,
+        target: &HashTarget,
+        plain: impl Into<Password<Plain>>,
+    ) -> std::result::Result<WeakFlag, VerifyError> {
+        if !verify_phc(&self.1, Some(target.argon2()), plain.into().as_bytes())? {
+            return Err(VerifyError::Mismatch);
+        }
+
+        let params = self.params().map_err(VerifyError::InvalidHash)?;
+        let target = &target.params;
+        match params.m_cost() < target.m_cost()
+            || params.t_cost() < target.t_cost()
+            || params.p_cost() < target.p_cost()
+        {
+            true => Ok(WeakFlag::Weak),
+            false => Ok(WeakFlag::Strong),
+        }
+    }
+}
+
+/// Flags which stored hashes should be rehashed with the target configuration, such as when
+/// planning an algorithm upgrade.
+///
+/// - `entries`: The stored hashes, each with an identifier such as a user ID.
+/// - `target`: The configuration new hashes are produced with.
+///
+/// Hashes cannot be upgraded without the plain text passwords, so flagged entries are meant to
+/// be rehashed on their next successful login, or have their passwords reset. Hashes produced
+/// with another algorithm or version than the target are flagged, as are hashes that cannot be
+/// parsed, such as bcrypt hashes, since they never meet the target.
+///
+/// Returns the identifiers in the same order, each with `true` if `needs_rehash` reports the
+/// hash should be rehashed.
+pub fn plan_rehash<Id>(
+    entries: impl IntoIterator<Item = (Id, Password<Hashed>)>,
+    target: &HashTarget,
+) -> Vec<(Id, bool)> {
+    entries
+        .into_iter()
+        .map(|(id, hashed)| (id, hashed.needs_rehash(target).unwrap_or(true)))
+        .collect()
+}
+
+/// Reports the distribution of algorithms and work factors across stored hashes, such as for
+/// a security audit looking for downgraded hashes.
+///
+/// - `hashes`: The stored hashes to analyze.
+///
+/// Work factors are only collected from Argon2 hashes whose parameters can be parsed, as with
+/// `params`. Hashes of other algorithms are counted separately, since their parameters have
+/// other meanings. Nothing is verified or rehashed.
+///
+/// Returns a `HashAudit` with the number of hashes per algorithm and the statistics of the
+/// memory costs and iteration counts of the Argon2 hashes.
+pub fn analyze<'a>(hashes: impl IntoIterator<Item = &'a Password<Hashed>>) -> HashAudit {
+    let mut audit = HashAudit::default();
+    let mut memory_kib = Vec::new();
+    let mut iterations = Vec::new();
+
+    for hashed in hashes {
+        match hashed.algorithm() {
+            Some(algorithm) => *audit.algorithms.entry(algorithm).or_default() += 1,
+            None => audit.unknown += 1,
+        }
+        if !hashed.is_argon2() {
+            audit.non_argon2 += usize::from(hashed.algorithm().is_some());
+            continue;
+        }
+        if let Ok(params) = hashed.params() {
+            memory_kib.push(params.m_cost());
+            iterations.push(params.t_cost());
+        }
+    }
+
+    audit.memory_kib = WorkFactorStats::from_values(memory_kib);
+    audit.iterations = WorkFactorStats::from_values(iterations);
+    audit
+}
+
+/// Parses an Argon2 PHC string and its parameters, rejecting hashes of other algorithms.
+fn parse_argon2(value: &str) -> Result<(PasswordHash<'_>, Params)> {
+    let hash = PasswordHash::new(value)?;
+    Algorithm::try_from(hash.algorithm)?;
+    let params = Params::try_from(&hash)?;
+    Ok((hash, params))
+}
+
+/// Retrieves the version of a parsed hash, defaulting to the version `argon2` assumes when
+/// hashing and verifying PHC strings without one.
+fn version(hash: &PasswordHash) -> u32 {
+    hash.version.unwrap_or(Version::default().into())
+}
+
+/// Parses the hash portion of the PHC string.
+fn hash_output(value: &str) -> Result<Output> {
+    PasswordHash::new(value)?.hash.ok_or(Error::PhcStringField)
+}
+
+/// Retrieves the output length of the parameters, falling back to the Argon2 default.
+fn output_len(params: &Params) -> usize {
+    params.output_len().unwrap_or(Params::DEFAULT_OUTPUT_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, plan_rehash, HashTarget, WeakFlag, WorkFactorStats};
+    use crate::{HashAlgorithm, Hashed, Password, Plain, VerifyError};
+    use argon2::{
+        password_hash::{Error, SaltString},
+        Algorithm, Argon2, Params, Version,
+    };
+
+    /// Hash of `"Password"` with `m=8,t=1,p=1`.
+    const WEAK_HASH: &str = "$argon2id$v=19$m=8,t=1,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$VSGjAZoWLd8Ds9tkbDEx49KABqrdxsMoISbYU31PMbM";
+    /// Hash of `"Password"` with `m=16,t=2,p=1`.
+    const TARGET_HASH: &str = "$argon2id$v=19$m=16,t=2,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$A1m+JXG5mATZnxD/jMU2L9lJwvRvQKLVrfXeQ0XaDY8";
+    /// Hash of `"Password"` with `m=32,t=3,p=1`.
+    const STRONG_HASH: &str = "$argon2id$v=19$m=32,t=3,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$6niUAqDGwNpAlBm3zKOJUCWHjHsdo0U3DW3k8JlwnLw";
+
+    fn target() -> HashTarget {
+        HashTarget::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            Params::new(16, 2, 1, None).expect("Argon2 parameters should be valid."),
+        )
+    }
+
+    #[test]
+    fn params_and_rehash_detection() {
+        let params = Password::<Hashed>::new_unchecked(STRONG_HASH)
+            .params()
+            .expect("Parsing should not fail.");
+
+        assert_eq!(
+            (params.m_cost(), params.t_cost(), params.p_cost()),
+            (32, 3, 1)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked("$scrypt$m=32,t=3,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$6niUAqDGwNpAlBm3zKOJUCWHjHsdo0U3DW3k8JlwnLw")
+                .params(),
+            Err(Error::Algorithm)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(TARGET_HASH).needs_rehash(&target()),
+            Ok(false)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(WEAK_HASH).needs_rehash(&target()),
+            Ok(true)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(STRONG_HASH).needs_rehash(&target()),
+            Ok(true)
+        );
+        assert!(Password::<Hashed>::new_unchecked("not a hash")
+            .needs_rehash(&target())
+            .is_err());
+    }
+
+    #[test]
+    fn rehash_defaults_missing_version() {
+        let unversioned = Password::<Hashed>::new_unchecked(TARGET_HASH.replace("v=19$", ""));
+
+        assert!(unversioned.matches(&Password::new("Password")));
+        assert_eq!(unversioned.needs_rehash(&target()), Ok(false));
+        assert_eq!(
+            unversioned.needs_rehash(&HashTarget {
+                version: Version::V0x10,
+                ..target()
+            }),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn rehash_detects_algorithm_and_version() {
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let hash_with = |algorithm, version| {
+            Password::<Plain>::new("Password")
+                .hash(
+                    Some(Argon2::new(algorithm, version, target().params.clone())),
+                    &salt,
+                )
+                .expect("Argon2 encoding should not fail.")
+        };
+
+        assert_eq!(
+            hash_with(Algorithm::Argon2id, Version::V0x13).needs_rehash(&target()),
+            Ok(false)
+        );
+        assert_eq!(
+            hash_with(Algorithm::Argon2i, Version::V0x13).needs_rehash(&target()),
+            Ok(true)
+        );
+        assert_eq!(
+            hash_with(Algorithm::Argon2id, Version::V0x10).needs_rehash(&target()),
+            Ok(true)
+        );
+        assert_eq!(
+            Password::<Plain>::new("Password")
+                .hash(
+                    Some(Argon2::new(
+                        Algorithm::Argon2i,
+                        Version::V0x13,
+                        Params::default()
+                    )),
+                    &salt
+                )
+                .expect("Argon2 encoding should not fail.")
+                .needs_rehash(&HashTarget::default()),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn minimum_work_factors() {
+        let meets = |hash, memory, iterations| {
+            Password::<Hashed>::new_unchecked(hash)
+                .meets_minimums(memory, iterations)
+                .expect("Parsing should not fail.")
+        };
+
+        assert!(meets(TARGET_HASH, 16, 2));
+        assert!(meets(STRONG_HASH, 16, 2));
+        assert!(!meets(WEAK_HASH, 16, 2));
+        assert!(!meets(STRONG_HASH, 64, 1));
+        assert!(!meets(STRONG_HASH, 8, 4));
+        assert!(Password::<Hashed>::new_unchecked("not a hash")
+            .meets_minimums(8, 1)
+            .is_err());
+    }
+
+    #[test]
+    fn rehash_plan_flags_weak_hashes() {
+        let entries = [
+            (1, WEAK_HASH),
+            (2, TARGET_HASH),
+            (3, STRONG_HASH),
+            (4, "not a hash"),
+            (5, TARGET_HASH),
+        ]
+        .map(|(id, hash)| (id, Password::<Hashed>::new_unchecked(hash)));
+
+        assert_eq!(
+            plan_rehash(entries, &target()),
+            vec![(1, true), (2, false), (3, true), (4, true), (5, false)]
+        );
+    }
+
+    #[test]
+    fn rehash_plan_flags_algorithm_upgrades() {
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let argon2i = Password::<Plain>::new("Password")
+            .hash(
+                Some(Argon2::new(
+                    Algorithm::Argon2i,
+                    Version::V0x13,
+                    target().params.clone(),
+                )),
+                &salt,
+            )
+            .expect("Argon2 encoding should not fail.");
+        let entries = [
+            ("argon2i", argon2i),
+            ("argon2id", Password::<Hashed>::new_unchecked(TARGET_HASH)),
+            (
+                "bcrypt",
+                Password::<Hashed>::new_unchecked(
+                    "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW",
+                ),
+            ),
+        ];
+
+        assert_eq!(
+            plan_rehash(entries, &target()),
+            vec![("argon2i", true), ("argon2id", false), ("bcrypt", true)]
+        );
+    }
+
+    #[test]
+    fn audit_reports_work_factor_distribution() {
+        let hashes = [
+            WEAK_HASH,
+            STRONG_HASH,
+            WEAK_HASH,
+            "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW",
+            "not a hash",
+            STRONG_HASH,
+            STRONG_HASH,
+            "$scrypt$m=65536,t=9,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$6niUAqDGwNpAlBm3zKOJUCWHjHsdo0U3DW3k8JlwnLw",
+            "$balloon$m=65536,t=9,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$6niUAqDGwNpAlBm3zKOJUCWHjHsdo0U3DW3k8JlwnLw",
+        ]
+        .map(Password::<Hashed>::new_unchecked);
+
+        let audit = analyze(&hashes);
+
+        assert_eq!(audit.algorithms.get(&HashAlgorithm::Argon2id), Some(&5));
+        assert_eq!(audit.algorithms.get(&HashAlgorithm::Bcrypt), Some(&1));
+        assert_eq!(audit.algorithms.get(&HashAlgorithm::Scrypt), Some(&1));
+        assert_eq!(audit.unknown, 2);
+        assert_eq!(audit.non_argon2, 2);
+        assert_eq!(
+            audit.memory_kib,
+            Some(WorkFactorStats {
+                min: 8,
+                median: 32,
+                max: 32
+            })
+        );
+        assert_eq!(
+            audit.iterations,
+            Some(WorkFactorStats {
+                min: 1,
+                median: 3,
+                max: 3
+            })
+        );
+        assert_eq!(
+            analyze(&hashes[..2]).memory_kib.map(|stats| stats.median),
+            Some(8)
+        );
+        assert_eq!(analyze([]).iterations, None);
+    }
+
+    #[test]
+    fn describe_configuration() {
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(WEAK_HASH).describe(),
+            Ok("argon2id v19 m=8 t=1 p=1".to_owned())
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(STRONG_HASH).describe(),
+            Ok("argon2id v19 m=32 t=3 p=1".to_owned())
+        );
+        assert!(!Password::<Hashed>::new_unchecked(STRONG_HASH)
+            .describe()
+            .expect("Parsing should not fail.")
+            .contains("dGFnX3Bhc3N3b3JkX3NhbHQ"));
+        assert!(Password::<Hashed>::new_unchecked("not a hash")
+            .describe()
+            .is_err());
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(STRONG_HASH.replace("argon2id", "scrypt")).describe(),
+            Err(Error::Algorithm)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(STRONG_HASH.replace("v=19$", "")).describe(),
+            Ok("argon2id v19 m=32 t=3 p=1".to_owned())
+        );
+    }
+
+    #[test]
+    fn digest_and_output_len() {
+        let hashed_password = Password::<Hashed>::new_unchecked(WEAK_HASH);
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let short_password = Password::<Plain>::new("Password")
+            .hash(
+                Some(Argon2::new(
+                    Algorithm::Argon2id,
+                    Version::V0x13,
+                    Params::new(8, 1, 1, Some(16)).expect("Argon2 parameters should be valid."),
+                )),
+                &salt,
+            )
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(hashed_password.output_len(), Ok(Params::DEFAULT_OUTPUT_LEN));
+        assert_eq!(
+            hashed_password.digest().map(|digest| digest[..4].to_vec()),
+            Ok(vec![0x55, 0x21, 0xa3, 0x01])
+        );
+        assert_eq!(short_password.output_len(), Ok(16));
+        assert_eq!(short_password.digest().map(|digest| digest.len()), Ok(16));
+        assert!(
+            Password::<Hashed>::new_unchecked("$argon2id$v=19$m=8,t=1,p=1")
+                .digest()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn verification_flags_weak_hashes() {
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(STRONG_HASH)
+                .verify_flagging_weak(&target(), "Password".to_owned()),
+            Ok(WeakFlag::Strong)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(WEAK_HASH)
+                .verify_flagging_weak(&target(), "Password".to_owned()),
+            Ok(WeakFlag::Weak)
+        );
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(WEAK_HASH)
+                .verify_flagging_weak(&target(), "Wrong".to_owned()),
+            Err(VerifyError::Mismatch)
+        );
+    }
+}
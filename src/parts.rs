@@ -0,0 +1,102 @@
+//! Hashed passwords stored split across separate columns.
+
+use crate::{compact::CompactHash, Hashed, Password};
+use argon2::password_hash::{Error, Result};
+
+/// Fields of a hashed password stored separately, such as in a schema predating PHC strings.
+///
+/// The salt and digest are raw bytes, as opposed to the Base64 encoding of PHC strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredHashParts {
+    /// Identifier of the algorithm, such as `"argon2id"`.
+    pub algorithm: String,
+    /// Version of the algorithm, such as `19`, if any.
+    pub version: Option<u32>,
+    /// Parameters of the algorithm, such as `"m=19456,t=2,p=1"`.
+    pub params: String,
+    /// Raw salt bytes.
+    pub salt: Vec<u8>,
+    /// Raw digest bytes.
+    pub digest: Vec<u8>,
+}
+
+impl Password<Hashed> {
+    /// Reassembles a hashed password from its separately stored fields.
+    ///
+    /// - `parts`: The fields of the hash.
+    ///
+    /// Produces a result containing the hashed password as a PHC string if successful, which
+    /// can then be verified as any other hashed password. If a field is invalid, an
+    /// `argon2::password_hash::Result` with an error is returned.
+    pub fn from_parts(parts: &StoredHashParts) -> Result<Self> {
+        let compact = CompactHash {
+            algorithm: parts.algorithm.clone(),
+            version: parts.version,
+            params: parts.params.clone(),
+            salt: Some(parts.salt.clone()),
+            hash: Some(parts.digest.clone()),
+        };
+        Password::from_phc(compact.to_phc()?)
+    }
+
+    /// Splits the hashed password into fields that can be stored separately.
+    ///
+    /// Produces a result containing the fields if successful. If the hash cannot be parsed or
+    /// has no salt or digest, an `argon2::password_hash::Result` with an error is returned.
+    pub fn to_parts(&self) -> Result<StoredHashParts> {
+        let compact = CompactHash::from_phc(&self.1)?;
+
+        Ok(StoredHashParts {
+            algorithm: compact.algorithm,
+            version: compact.version,
+            params: compact.params,
+            salt: compact.salt.ok_or(Error::PhcStringField)?,
+            digest: compact.hash.ok_or(Error::PhcStringField)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StoredHashParts;
+    use crate::{HashAlgorithm, Hashed, Password, TEST_HASH};
+
+    fn parts() -> StoredHashParts {
+        StoredHashParts {
+            algorithm: "argon2id".to_owned(),
+            version: Some(19),
+            params: "m=8,t=1,p=1".to_owned(),
+            salt: b"tag_password_salt".to_vec(),
+            digest: Password::<Hashed>::new_unchecked(TEST_HASH)
+                .digest()
+                .expect("Parsing should not fail."),
+        }
+    }
+
+    #[test]
+    fn parts_reassemble_into_verifiable_hash() {
+        let hashed_password = Password::from_parts(&parts()).expect("Reassembly should not fail.");
+
+        assert_eq!(hashed_password.raw(), TEST_HASH);
+        assert_eq!(hashed_password.algorithm(), Some(HashAlgorithm::Argon2id));
+        assert!(hashed_password.matches(&Password::new("Password")));
+        assert!(Password::from_parts(&StoredHashParts {
+            algorithm: "not an algorithm!".to_owned(),
+            ..parts()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn hash_decomposes_into_parts() {
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(TEST_HASH).to_parts(),
+            Ok(parts())
+        );
+        assert!(
+            Password::<Hashed>::new_unchecked("$argon2id$v=19$m=8,t=1,p=1")
+                .to_parts()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Diceware-style passphrase generation from user-supplied wordlists.
+
+use crate::{random_index, Password, Plain};
+use argon2::password_hash::rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "os-rng")]
+use rand_core::OsRng;
+use std::io::{self, BufRead};
+
+/// List of distinct words passphrases are generated from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Wordlist(Vec<String>);
+
+impl Wordlist {
+    /// Loads a wordlist from the provided reader.
+    ///
+    /// - `reader`: The source of the wordlist, with one word per line.
+    ///
+    /// The last whitespace separated token of each line is used as the word, so both plain
+    /// lists and diceware lists such as `11111 abacus` are accepted. Blank lines and duplicate
+    /// words are skipped.
+    ///
+    /// Returns the `Wordlist`, or an `io::Error` if reading fails or fewer than two distinct
+    /// words are found.
+    pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+        let mut words = Vec::new();
+        for line in reader.lines() {
+            if let Some(word) = line?.split_whitespace().last() {
+                if !words.iter().any(|known| known == word) {
+                    words.push(word.to_owned());
+                }
+            }
+        }
+
+        match words.len() {
+            0 | 1 => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "wordlist must contain at least two distinct words",
+            )),
+            _ => Ok(Wordlist(words)),
+        }
+    }
+
+    /// Returns the number of words in the list.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list contains no words, which `from_reader` never produces.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Computes the entropy of a passphrase of the provided number of words, in bits.
+    pub fn entropy_bits(&self, words: usize) -> f64 {
+        words as f64 * (self.0.len() as f64).log2()
+    }
+}
+
+impl Password<Plain> {
+    /// Generates a new random passphrase.
+    ///
+    /// - `words`: The number of words in the passphrase.
+    /// - `wordlist`: The words to pick from.
+    /// - `separator`: The text placed between words.
+    ///
+    /// Returns a new `Password` instance generated with the operating system's random number
+    /// generator.
+    #[cfg(feature = "os-rng")]
+    pub fn generate_passphrase(words: usize, wordlist: &Wordlist, separator: &str) -> Self {
+        Self::generate_passphrase_with_rng(&mut OsRng, words, wordlist, separator)
+    }
+
+    /// Generates a new random passphrase using the provided random number generator.
+    ///
+    /// - `rng`: A cryptographically secure random number generator.
+    /// - `words`: The number of words in the passphrase.
+    /// - `wordlist`: The words to pick from.
+    /// - `separator`: The text placed between words.
+    ///
+    /// Words are picked uniformly and independently, so the passphrase has
+    /// `Wordlist::entropy_bits` bits of entropy.
+    ///
+    /// Returns a new `Password` instance containing the generated passphrase.
+    pub fn generate_passphrase_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        rng: &mut R,
+        words: usize,
+        wordlist: &Wordlist,
+        separator: &str,
+    ) -> Self {
+        let value = (0..words)
+            .map(|_| wordlist.0[random_index(rng, wordlist.0.len())].as_str())
+            .collect::<Vec<_>>()
+            .join(separator);
+        Password::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Wordlist;
+    use crate::{Password, Plain};
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use std::io::Cursor;
+
+    const WORDLIST: &str =
+        "11111\tabacus\n11112\tabdomen\n11113\tabide\n\n11114 ability\nable\nable\n";
+
+    #[test]
+    fn wordlist_from_reader() {
+        let wordlist =
+            Wordlist::from_reader(Cursor::new(WORDLIST)).expect("Loading should not fail.");
+
+        assert_eq!(wordlist.len(), 5);
+        assert!((wordlist.entropy_bits(2) - 2.0 * 5f64.log2()).abs() < 1e-9);
+        assert!(Wordlist::from_reader(Cursor::new("only\nonly\n")).is_err());
+    }
+
+    #[test]
+    fn passphrase_generation() {
+        let wordlist =
+            Wordlist::from_reader(Cursor::new(WORDLIST)).expect("Loading should not fail.");
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+
+        let first = Password::<Plain>::generate_passphrase_with_rng(&mut rng, 6, &wordlist, "-");
+        let second = Password::<Plain>::generate_passphrase_with_rng(&mut rng, 6, &wordlist, "-");
+
+        assert_eq!(first.raw().split('-').count(), 6);
+        assert!(first
+            .raw()
+            .split('-')
+            .all(|word| ["abacus", "abdomen", "abide", "ability", "able"].contains(&word)));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    #[cfg(feature = "os-rng")]
+    fn passphrase_generation_with_os_rng() {
+        let wordlist =
+            Wordlist::from_reader(Cursor::new(WORDLIST)).expect("Loading should not fail.");
+
+        let first = Password::<Plain>::generate_passphrase(8, &wordlist, " ");
+        let second = Password::<Plain>::generate_passphrase(8, &wordlist, " ");
+
+        assert_eq!(first.word_count(), 8);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,283 @@
+//! Server-side peppers mixed into Argon2 as a secret key.
+
+use crate::{HashError, Hashed, Password, Plain, VerifyError};
+use argon2::{
+    password_hash::{Result, Salt},
+    Algorithm, Argon2, KeyId, Params, ParamsBuilder, PasswordHash, Version,
+};
+use std::fmt::{Debug, Formatter};
+
+/// Secret value mixed into every hash, stored outside of the password database.
+///
+/// A pepper is passed to Argon2 as its secret key, so a leaked database alone is not enough to
+/// brute-force the hashes. Its value is never shown by `Debug`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Pepper(Vec<u8>);
+
+impl Pepper {
+    /// Creates a new `Pepper` from the provided secret bytes.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Pepper(secret.into())
+    }
+
+    /// Retrieves the secret bytes of the pepper.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Builds an Argon2 configuration using the pepper as its secret key.
+    fn argon2<'k>(
+        &'k self,
+        algorithm: Algorithm,
+        version: Version,
+        params: Params,
+    ) -> Result<Argon2<'k>> {
+        Ok(Argon2::new_with_secret(
+            &self.0, algorithm, version, params,
+        )?)
+    }
+}
+
+impl Debug for Pepper {
+    /// Formats the `Pepper` for debugging purposes, without revealing its value.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pepper(<redacted>)")
+    }
+}
+
+/// Hasher applying a pepper to every hash and verification.
+///
+/// Unlike `hash_with_pepper_versioned` and `verify_with_pepper`, which take the pepper as an
+/// argument, the pepper is part of the hasher, so code holding a `PepperedHasher` cannot hash
+/// or verify a password without it. Its value is never shown by `Debug`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PepperedHasher {
+    pepper: Pepper,
+    algorithm: Algorithm,
+    version: Version,
+    params: Params,
+}
+
+impl PepperedHasher {
+    /// Creates a new `PepperedHasher`, configured as `Argon2::new` would be.
+    ///
+    /// - `pepper`: The secret bytes of the pepper.
+    /// - `algorithm`: The Argon2 variant used for hashing.
+    /// - `version`: The Argon2 version used for hashing.
+    /// - `params`: The Argon2 parameters used for hashing.
+    pub fn new(
+        pepper: impl Into<Vec<u8>>,
+        algorithm: Algorithm,
+        version: Version,
+        params: Params,
+    ) -> Self {
+        PepperedHasher {
+            pepper: Pepper::new(pepper),
+            algorithm,
+            version,
+            params,
+        }
+    }
+
+    /// Hashes the password with the pepper.
+    ///
+    /// - `plain`: The plain text password to hash.
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    pub fn hash<'a>(
+        &self,
+        plain: &Password<Plain>,
+        salt: impl Into<Salt<'a>>,
+    ) -> std::result::Result<Password<Hashed>, HashError> {
+        plain.hash_shared(
+            &self
+                .pepper
+                .argon2(self.algorithm, self.version, self.params.clone())?,
+            salt,
+        )
+    }
+
+    /// Checks whether the hashed password matches the plain text password with the pepper.
+    ///
+    /// - `hashed`: The hashed password, produced by a hasher with the same pepper.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// The algorithm, version and parameters recorded in the hash are used, so hashes produced
+    /// with another configuration still verify.
+    ///
+    /// Returns `Ok(true)` if the passwords match, `Ok(false)` otherwise, and a `VerifyError` if
+    /// the stored hash cannot be parsed or the backend fails.
+    pub fn verify(
+        &self,
+        hashed: &Password<Hashed>,
+        plain: &Password<Plain>,
+    ) -> std::result::Result<bool, VerifyError> {
+        let hash = PasswordHash::new(hashed.raw()).map_err(VerifyError::InvalidHash)?;
+        let argon2 = self
+            .pepper
+            .argon2(
+                self.algorithm,
+                self.version,
+                Params::try_from(&hash).map_err(VerifyError::InvalidHash)?,
+            )
+            .map_err(VerifyError::InvalidHash)?;
+        crate::verify_kdf_input(&argon2, &crate::prehash::kdf_input(plain.as_bytes()), &hash)
+    }
+}
+
+impl Password<Plain> {
+    /// Hashes the password with Argon2id and a pepper, recording the pepper version.
+    ///
+    /// - `pepper`: The pepper mixed into the hash.
+    /// - `version`: The version of the pepper, stored in the `keyid` parameter of the hash.
+    /// - `params`: Optional Argon2 parameters, the defaults being used otherwise.
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// The recorded version, available through `pepper_version`, tells which pepper must be
+    /// used to verify the hash, which allows rotating peppers gracefully.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    pub fn hash_with_pepper_versioned<'a>(
+        &self,
+        pepper: &Pepper,
+        version: u8,
+        params: Option<Params>,
+        salt: impl Into<Salt<'a>>,
+    ) -> std::result::Result<Password<Hashed>, HashError> {
+        let params = params.unwrap_or_default();
+        let mut builder = ParamsBuilder::new();
+        builder
+            .m_cost(params.m_cost())
+            .t_cost(params.t_cost())
+            .p_cost(params.p_cost())
+            .keyid(KeyId::new(&[version]).map_err(invalid_params)?);
+        if let Some(output_len) = params.output_len() {
+            builder.output_len(output_len);
+        }
+
+        self.hash_shared(
+            &pepper.argon2(
+                Algorithm::Argon2id,
+                Version::V0x13,
+                builder.build().map_err(invalid_params)?,
+            )?,
+            salt,
+        )
+    }
+}
+
+/// Reports an error building the Argon2 parameters as `HashError::InvalidParams`.
+fn invalid_params(error: argon2::Error) -> HashError {
+    HashError::InvalidParams(error.into())
+}
+
+impl Password<Hashed> {
+    /// Retrieves the pepper version recorded by `hash_with_pepper_versioned`.
+    ///
+    /// Returns the version, or `None` if the hash cannot be parsed or records no version.
+    pub fn pepper_version(&self) -> Option<u8> {
+        match self.params().ok()?.keyid() {
+            [version] => Some(*version),
+            _ => None,
+        }
+    }
+
+    /// Verifies if the hashed password matches the provided plain text password using a pepper.
+    ///
+    /// - `pepper`: The pepper the password was hashed with.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Returns a result indicating success or failure of the verification process.
+    /// If successful, `Ok(())` is returned. If verification fails, an
+    /// `argon2::password_hash::Result` with an error is returned.
+    pub fn verify_with_pepper(
+        &self,
+        pepper: &Pepper,
+        plain: impl Into<Password<Plain>>,
+    ) -> Result<()> {
+        let hash = PasswordHash::new(&self.1)?;
+        crate::verify_kdf_input_phc(
+            &pepper.argon2(
+                Algorithm::Argon2id,
+                Version::V0x13,
+                Params::try_from(&hash)?,
+            )?,
+            &crate::prehash::kdf_input(plain.into().as_bytes()),
+            &hash,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pepper, PepperedHasher};
+    use crate::{Argon2Profile, Password, Plain};
+    use argon2::{password_hash::SaltString, Algorithm, Version};
+
+    #[test]
+    fn pepper_version_roundtrip() {
+        let pepper = Pepper::new(*b"pepper-v3");
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+
+        let hashed_password = Password::<Plain>::new("Password")
+            .hash_with_pepper_versioned(&pepper, 3, Some(Argon2Profile::Testing.params()), &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(hashed_password.pepper_version(), Some(3));
+        assert!(hashed_password
+            .verify_with_pepper(&pepper, Password::new("Password"))
+            .is_ok());
+        assert!(hashed_password
+            .verify_with_pepper(&Pepper::new(*b"pepper-v2"), Password::new("Password"))
+            .is_err());
+        assert!(hashed_password
+            .verify(None, Password::new("Password"))
+            .is_err());
+    }
+
+    #[test]
+    fn pepper_is_redacted() {
+        assert!(!format!("{:?}", Pepper::new(*b"secret")).contains("secret"));
+        assert_eq!(
+            Password::<crate::Hashed>::new_unchecked(crate::TEST_HASH).pepper_version(),
+            None
+        );
+    }
+
+    #[test]
+    fn peppered_hasher_requires_same_pepper() {
+        let hasher = PepperedHasher::new(
+            *b"pepper-v1",
+            Algorithm::Argon2d,
+            Version::V0x10,
+            Argon2Profile::Testing.params(),
+        );
+        let other = PepperedHasher::new(
+            *b"pepper-v2",
+            Algorithm::Argon2d,
+            Version::V0x10,
+            Argon2Profile::Testing.params(),
+        );
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+        let plain_password = Password::<Plain>::new("Password");
+
+        let hashed_password = hasher
+            .hash(&plain_password, &salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert!(hashed_password.raw().starts_with("$argon2d$v=16$"));
+        assert_eq!(hasher.verify(&hashed_password, &plain_password), Ok(true));
+        assert_eq!(
+            hasher.verify(&hashed_password, &Password::new("Wrong")),
+            Ok(false)
+        );
+        assert_eq!(other.verify(&hashed_password, &plain_password), Ok(false));
+        assert!(hasher
+            .verify(&Password::new_unchecked("not a hash"), &plain_password)
+            .is_err());
+        assert!(!format!("{hasher:?}").contains("pepper-v1"));
+    }
+}
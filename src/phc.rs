@@ -0,0 +1,64 @@
+//! Structural checks of PHC strings that do not depend on any hashing backend.
+
+/// Maximum number of `$` separated fields of a PHC string: identifier, version, parameters,
+/// salt and hash.
+const MAX_FIELDS: usize = 5;
+
+/// Checks whether the provided string looks like a PHC password hash, without parsing it.
+///
+/// - `value`: The string to check.
+///
+/// This is a lightweight check available regardless of features, meant for routing values in
+/// builds without a hashing backend: the string must start with `$`, have between one and five
+/// non-empty `$` separated fields, an identifier of lowercase letters, digits and `-`, and only
+/// characters allowed in PHC strings. Use `is_hashed_format` with the `argon2` feature enabled
+/// for a full parse.
+///
+/// Returns `true` if the value is structurally a PHC string.
+pub fn is_phc_format(value: &str) -> bool {
+    let Some(rest) = value.strip_prefix('$') else {
+        return false;
+    };
+    let fields: Vec<&str> = rest.split('$').collect();
+    let Some(ident) = fields.first() else {
+        return false;
+    };
+
+    fields.len() <= MAX_FIELDS
+        && ident.len() <= 32
+        && ident
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-')
+        && fields.iter().all(|field| {
+            !field.is_empty()
+                && field
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"+/=,.-".contains(&byte))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_phc_format;
+
+    #[test]
+    fn well_formed_phc_strings() {
+        assert!(is_phc_format(crate::TEST_HASH));
+        assert!(is_phc_format("$scrypt$ln=16,r=8,p=1$c2FsdA$aGFzaA"));
+        assert!(is_phc_format("$pbkdf2-sha256$i=1000$c2FsdA$aGFzaA"));
+        assert!(is_phc_format("$argon2id"));
+    }
+
+    #[test]
+    fn malformed_phc_strings() {
+        assert!(!is_phc_format(""));
+        assert!(!is_phc_format("$"));
+        assert!(!is_phc_format("Password"));
+        assert!(!is_phc_format("argon2id$v=19$m=8,t=1,p=1"));
+        assert!(!is_phc_format("$Argon2id$v=19"));
+        assert!(!is_phc_format("$argon2id$$m=8"));
+        assert!(!is_phc_format("$argon2id$v=19$m=8$salt$hash$extra"));
+        assert!(!is_phc_format("$argon2id$v=19$m=8 t=1"));
+        assert!(!is_phc_format("$argon2id$v=19$"));
+    }
+}
@@ -0,0 +1,581 @@
+//! Password policies describing the rules plain text passwords must satisfy.
+
+use crate::{Password, Plain};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Class of characters a password policy can require.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CharacterClass {
+    /// Lowercase letters.
+    Lowercase,
+    /// Uppercase letters.
+    Uppercase,
+    /// ASCII digits.
+    Digit,
+    /// Any character that is neither alphanumeric nor whitespace.
+    Symbol,
+}
+
+impl CharacterClass {
+    /// Checks whether the provided character belongs to this class.
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            CharacterClass::Lowercase => c.is_lowercase(),
+            CharacterClass::Uppercase => c.is_uppercase(),
+            CharacterClass::Digit => c.is_ascii_digit(),
+            CharacterClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+impl Display for CharacterClass {
+    /// Formats the `CharacterClass` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharacterClass::Lowercase => write!(f, "lowercase"),
+            CharacterClass::Uppercase => write!(f, "uppercase"),
+            CharacterClass::Digit => write!(f, "digit"),
+            CharacterClass::Symbol => write!(f, "symbol"),
+        }
+    }
+}
+
+/// Unit in which a `PasswordPolicy` counts the length of passwords.
+///
+/// More units are available with some features enabled, such as `Graphemes` with the
+/// `graphemes` feature, so matches on this enum need a wildcard arm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum LengthUnit {
+    /// UTF-8 encoded bytes, for legacy systems limiting the storage size of passwords.
+    Bytes,
+    /// Unicode scalar values.
+    #[default]
+    Chars,
+    /// Extended grapheme clusters, matching what users perceive as characters, such as an
+    /// emoji made of several scalar values.
+    #[cfg(feature = "graphemes")]
+    Graphemes,
+}
+
+impl LengthUnit {
+    /// Counts the length of the value in this unit.
+    pub fn count(&self, value: &str) -> usize {
+        match self {
+            LengthUnit::Bytes => value.len(),
+            LengthUnit::Chars => value.chars().count(),
+            #[cfg(feature = "graphemes")]
+            LengthUnit::Graphemes => {
+                unicode_segmentation::UnicodeSegmentation::graphemes(value, true).count()
+            }
+        }
+    }
+}
+
+impl Display for LengthUnit {
+    /// Formats the `LengthUnit` for displaying purposes, as used in violation messages.
+    ///
+    /// Grapheme clusters are what users perceive as characters, so they are displayed as such.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthUnit::Bytes => write!(f, "bytes"),
+            LengthUnit::Chars => write!(f, "characters"),
+            #[cfg(feature = "graphemes")]
+            LengthUnit::Graphemes => write!(f, "characters"),
+        }
+    }
+}
+
+/// Rules a plain text password must satisfy.
+///
+/// With the `serde` feature enabled, a policy serializes to a structure clients can use to
+/// display and pre-check the rules, keeping them in sync with the server, and deserializes
+/// from the same structure so it can be loaded from a configuration file. Fields missing from
+/// the input take their value from `PasswordPolicy::default`.
+///
+/// Lengths are counted in the unit of `length_unit`, characters by default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct PasswordPolicy {
+    /// Minimum length, in `length_unit`.
+    pub min_length: usize,
+    /// Maximum length, in `length_unit`, if any.
+    pub max_length: Option<usize>,
+    /// Character classes that must each appear at least once.
+    pub required_classes: Vec<CharacterClass>,
+    /// Unit in which the minimum and maximum lengths are counted.
+    pub length_unit: LengthUnit,
+}
+
+impl Default for PasswordPolicy {
+    /// Creates a policy requiring at least 8 characters, without any other rule.
+    fn default() -> Self {
+        PasswordPolicy {
+            min_length: 8,
+            max_length: None,
+            required_classes: Vec::new(),
+            length_unit: LengthUnit::Chars,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Sets the minimum length, in `length_unit`.
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Sets the maximum length, in `length_unit`.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Sets the unit in which the minimum and maximum lengths are counted.
+    pub fn length_unit(mut self, length_unit: LengthUnit) -> Self {
+        self.length_unit = length_unit;
+        self
+    }
+
+    /// Requires at least one character of the provided class.
+    pub fn require(mut self, class: CharacterClass) -> Self {
+        if !self.required_classes.contains(&class) {
+            self.required_classes.push(class);
+        }
+        self
+    }
+}
+
+/// Rule of a `PasswordPolicy` that a password failed to satisfy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// The password is shorter than the minimum.
+    TooShort {
+        /// The required minimum length, in `unit`.
+        min: usize,
+        /// The unit in which the length is counted.
+        unit: LengthUnit,
+    },
+    /// The password is longer than the maximum.
+    TooLong {
+        /// The allowed maximum length, in `unit`.
+        max: usize,
+        /// The unit in which the length is counted.
+        unit: LengthUnit,
+    },
+    /// The password does not contain any character of a required class.
+    MissingClass(CharacterClass),
+}
+
+impl Display for PolicyViolation {
+    /// Formats the `PolicyViolation` for displaying purposes.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyViolation::TooShort { min, unit } => {
+                write!(f, "password must be at least {min} {unit} long")
+            }
+            PolicyViolation::TooLong { max, unit } => {
+                write!(f, "password must be at most {max} {unit} long")
+            }
+            PolicyViolation::MissingClass(class) => {
+                let article = match class {
+                    CharacterClass::Uppercase => "an",
+                    CharacterClass::Lowercase | CharacterClass::Digit | CharacterClass::Symbol => {
+                        "a"
+                    }
+                };
+                write!(f, "password must contain {article} {class} character")
+            }
+        }
+    }
+}
+
+/// Single rule of a `PasswordPolicy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize),
+    serde(tag = "rule", rename_all = "snake_case")
+)]
+pub enum PolicyRule {
+    /// The password must be at least `min` long, counted in `unit`.
+    MinLength {
+        /// The required minimum length, in `unit`.
+        min: usize,
+        /// The unit in which the length is counted.
+        unit: LengthUnit,
+    },
+    /// The password must be at most `max` long, counted in `unit`.
+    MaxLength {
+        /// The allowed maximum length, in `unit`.
+        max: usize,
+        /// The unit in which the length is counted.
+        unit: LengthUnit,
+    },
+    /// The password must contain a character of `class`.
+    RequiredClass {
+        /// The required character class.
+        class: CharacterClass,
+    },
+}
+
+impl PolicyRule {
+    /// Retrieves the violation reported when a password fails this rule.
+    pub fn violation(&self) -> PolicyViolation {
+        match *self {
+            PolicyRule::MinLength { min, unit } => PolicyViolation::TooShort { min, unit },
+            PolicyRule::MaxLength { max, unit } => PolicyViolation::TooLong { max, unit },
+            PolicyRule::RequiredClass { class } => PolicyViolation::MissingClass(class),
+        }
+    }
+}
+
+/// Outcome of checking a password against a single rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RuleCheck {
+    /// The checked rule.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub rule: PolicyRule,
+    /// Whether the password satisfies the rule.
+    pub passed: bool,
+    /// A human-readable description of the rule, worded as a requirement.
+    pub message: String,
+}
+
+/// Breakdown of which rules of a `PasswordPolicy` a password passed or failed.
+///
+/// With the `serde` feature enabled, the report serializes to a structure form APIs can return
+/// so clients can highlight each failing rule.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PolicyReport {
+    /// The outcome of every rule of the policy, in the order of the policy.
+    pub rules: Vec<RuleCheck>,
+}
+
+impl PolicyReport {
+    /// Returns `true` if the password satisfies every rule.
+    pub fn passed(&self) -> bool {
+        self.rules.iter().all(|check| check.passed)
+    }
+
+    /// Retrieves the violations of the failed rules.
+    pub fn violations(&self) -> Vec<PolicyViolation> {
+        self.rules
+            .iter()
+            .filter(|check| !check.passed)
+            .map(|check| check.rule.violation())
+            .collect()
+    }
+}
+
+impl Password<Plain> {
+    /// Checks the password against every rule of the provided policy.
+    ///
+    /// - `policy`: The rules the password must satisfy.
+    ///
+    /// Returns a `PolicyReport` with the outcome of each rule, including the satisfied ones.
+    pub fn check(&self, policy: &PasswordPolicy) -> PolicyReport {
+        let unit = policy.length_unit;
+        let length = unit.count(&self.1);
+        let mut rules = vec![(
+            PolicyRule::MinLength {
+                min: policy.min_length,
+                unit,
+            },
+            length >= policy.min_length,
+        )];
+        if let Some(max) = policy.max_length {
+            rules.push((PolicyRule::MaxLength { max, unit }, length <= max));
+        }
+        for class in &policy.required_classes {
+            rules.push((
+                PolicyRule::RequiredClass { class: *class },
+                self.1.chars().any(|c| class.contains(c)),
+            ));
+        }
+
+        PolicyReport {
+            rules: rules
+                .into_iter()
+                .map(|(rule, passed)| RuleCheck {
+                    rule,
+                    passed,
+                    message: rule.violation().to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Validates the password against the provided policy.
+    ///
+    /// - `policy`: The rules the password must satisfy.
+    ///
+    /// Returns `Ok(())` if every rule is satisfied, or the list of violated rules otherwise.
+    pub fn validate(&self, policy: &PasswordPolicy) -> Result<(), Vec<PolicyViolation>> {
+        let violations = self.check(policy).violations();
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharacterClass, LengthUnit, PasswordPolicy, PolicyViolation};
+    use crate::{Password, Plain};
+
+    #[test]
+    fn policy_validation() {
+        let policy = PasswordPolicy::default()
+            .min_length(10)
+            .max_length(16)
+            .require(CharacterClass::Digit)
+            .require(CharacterClass::Uppercase);
+
+        assert_eq!(
+            Password::<Plain>::new("Password1234").validate(&policy),
+            Ok(())
+        );
+        assert_eq!(
+            Password::<Plain>::new("password").validate(&policy),
+            Err(vec![
+                PolicyViolation::TooShort {
+                    min: 10,
+                    unit: LengthUnit::Chars
+                },
+                PolicyViolation::MissingClass(CharacterClass::Digit),
+                PolicyViolation::MissingClass(CharacterClass::Uppercase),
+            ])
+        );
+        assert_eq!(
+            Password::<Plain>::new("Password1234567890").validate(&policy),
+            Err(vec![PolicyViolation::TooLong {
+                max: 16,
+                unit: LengthUnit::Chars
+            }])
+        );
+    }
+
+    #[test]
+    fn length_units() {
+        let emoji: Password<Plain> = Password::new("\u{1F511}\u{1F512}\u{1F513}");
+        let chars = PasswordPolicy::default().min_length(3);
+        let bytes = PasswordPolicy::default()
+            .min_length(20)
+            .length_unit(LengthUnit::Bytes);
+
+        assert_eq!(emoji.validate(&chars), Ok(()));
+        assert_eq!(
+            emoji.validate(&bytes),
+            Err(vec![PolicyViolation::TooShort {
+                min: 20,
+                unit: LengthUnit::Bytes
+            }])
+        );
+        assert_eq!(
+            emoji.validate(
+                &PasswordPolicy::default()
+                    .max_length(11)
+                    .min_length(0)
+                    .length_unit(LengthUnit::Bytes)
+            ),
+            Err(vec![PolicyViolation::TooLong {
+                max: 11,
+                unit: LengthUnit::Bytes
+            }])
+        );
+    }
+
+    #[test]
+    fn length_messages_render_unit() {
+        let too_short = |unit| PolicyViolation::TooShort { min: 12, unit }.to_string();
+
+        assert_eq!(
+            too_short(LengthUnit::Bytes),
+            "password must be at least 12 bytes long"
+        );
+        assert_eq!(
+            too_short(LengthUnit::Chars),
+            "password must be at least 12 characters long"
+        );
+        assert_eq!(
+            PolicyViolation::TooLong {
+                max: 64,
+                unit: LengthUnit::Bytes
+            }
+            .to_string(),
+            "password must be at most 64 bytes long"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "graphemes")]
+    fn grapheme_length_unit() {
+        let family: Password<Plain> = Password::new("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}");
+        let policy = PasswordPolicy::default().min_length(0).max_length(1);
+
+        assert_eq!(LengthUnit::Graphemes.count(family.raw()), 1);
+        assert_eq!(LengthUnit::Chars.count(family.raw()), 5);
+        assert!(family
+            .validate(&policy.clone().length_unit(LengthUnit::Graphemes))
+            .is_ok());
+        assert!(family.validate(&policy).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn policy_serializes_to_json() {
+        let policy = PasswordPolicy::default()
+            .min_length(12)
+            .max_length(64)
+            .require(CharacterClass::Lowercase)
+            .require(CharacterClass::Symbol);
+
+        let json = serde_json::to_value(&policy).expect("Serialization should not fail.");
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "min_length": 12,
+                "max_length": 64,
+                "required_classes": ["lowercase", "symbol"],
+                "length_unit": "chars",
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn policy_loads_from_toml_config() {
+        let policy: PasswordPolicy = toml::from_str(
+            r#"
+            min_length = 10
+            max_length = 32
+            required_classes = ["digit", "uppercase"]
+            "#,
+        )
+        .expect("Deserialization should not fail.");
+
+        assert_eq!(
+            policy,
+            PasswordPolicy::default()
+                .min_length(10)
+                .max_length(32)
+                .require(CharacterClass::Digit)
+                .require(CharacterClass::Uppercase)
+        );
+        assert_eq!(
+            Password::<Plain>::new("password1").validate(&policy),
+            Err(vec![
+                PolicyViolation::TooShort {
+                    min: 10,
+                    unit: LengthUnit::Chars
+                },
+                PolicyViolation::MissingClass(CharacterClass::Uppercase),
+            ])
+        );
+        assert!(Password::<Plain>::new("Password1234")
+            .validate(&policy)
+            .is_ok());
+        assert_eq!(
+            toml::from_str::<PasswordPolicy>(""),
+            Ok(PasswordPolicy::default())
+        );
+        assert!(toml::from_str::<PasswordPolicy>("min_lenght = 10").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn report_serializes_mixed_results() {
+        use super::PolicyRule;
+
+        let policy = PasswordPolicy::default()
+            .min_length(10)
+            .require(CharacterClass::Digit)
+            .require(CharacterClass::Uppercase);
+
+        let report = Password::<Plain>::new("password1").check(&policy);
+
+        assert!(!report.passed());
+        assert_eq!(
+            report.violations(),
+            vec![
+                PolicyViolation::TooShort {
+                    min: 10,
+                    unit: LengthUnit::Chars
+                },
+                PolicyViolation::MissingClass(CharacterClass::Uppercase),
+            ]
+        );
+        assert_eq!(
+            report.rules[1].rule,
+            PolicyRule::RequiredClass {
+                class: CharacterClass::Digit
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&report).expect("Serialization should not fail."),
+            serde_json::json!({
+                "rules": [
+                    {
+                        "rule": "min_length",
+                        "min": 10,
+                        "unit": "chars",
+                        "passed": false,
+                        "message": "password must be at least 10 characters long",
+                    },
+                    {
+                        "rule": "required_class",
+                        "class": "digit",
+                        "passed": true,
+                        "message": "password must contain a digit character",
+                    },
+                    {
+                        "rule": "required_class",
+                        "class": "uppercase",
+                        "passed": false,
+                        "message": "password must contain an uppercase character",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn missing_class_messages_use_articles() {
+        let message = |class| PolicyViolation::MissingClass(class).to_string();
+
+        assert_eq!(
+            message(CharacterClass::Uppercase),
+            "password must contain an uppercase character"
+        );
+        assert_eq!(
+            message(CharacterClass::Lowercase),
+            "password must contain a lowercase character"
+        );
+        assert_eq!(
+            message(CharacterClass::Digit),
+            "password must contain a digit character"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Transparent pre-hashing of long passwords.
+//!
+//! With the `prehash-long` feature enabled, passwords longer than `PREHASH_THRESHOLD` are
+//! replaced by their SHA-512 digest before being passed to the key derivation function, both
+//! when hashing and when verifying. This bounds the cost of hashing very long inputs, which
+//! could otherwise be used for denial of service, and keeps the whole password significant for
+//! backends that truncate their input, such as bcrypt at 72 bytes.
+//!
+//! The threshold is part of the stored hash format: a different threshold, or toggling the
+//! feature, breaks verification of every stored password whose length lies between the old
+//! and new thresholds. It is therefore a constant rather than a runtime setting.
+
+use std::borrow::Cow;
+
+/// Length, in bytes, above which passwords are pre-hashed with SHA-512.
+///
+/// It is above the 64 bytes of a SHA-512 digest, so a pre-hashed input is never pre-hashed
+/// again.
+#[cfg(feature = "prehash-long")]
+pub const PREHASH_THRESHOLD: usize = 128;
+
+/// Returns the bytes passed to the key derivation function for the provided password.
+///
+/// This is the SHA-512 digest of the password if it exceeds `PREHASH_THRESHOLD`, and the
+/// password itself otherwise or without the `prehash-long` feature.
+pub(crate) fn kdf_input(plain: &[u8]) -> Cow<'_, [u8]> {
+    #[cfg(feature = "prehash-long")]
+    if plain.len() > PREHASH_THRESHOLD {
+        use sha2::{Digest, Sha512};
+        return Cow::Owned(Sha512::digest(plain).to_vec());
+    }
+    Cow::Borrowed(plain)
+}
+
+#[cfg(all(test, feature = "prehash-long", feature = "argon2"))]
+mod tests {
+    use super::{kdf_input, PREHASH_THRESHOLD};
+    use crate::{Argon2Profile, Password, Plain};
+    use argon2::password_hash::SaltString;
+
+    fn roundtrip(len: usize) {
+        let plain_password: Password<Plain> = Password::new("a".repeat(len));
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+
+        let hashed_password = plain_password
+            .hash_fast(&salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(
+            hashed_password.try_verify(Some(Argon2Profile::Testing.argon2()), plain_password),
+            Ok(true)
+        );
+        assert_eq!(
+            hashed_password.try_verify(None, Password::new("a".repeat(len + 1))),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn prehash_below_threshold() {
+        let len = PREHASH_THRESHOLD;
+
+        assert_eq!(kdf_input("a".repeat(len).as_bytes()).len(), len);
+        roundtrip(len);
+    }
+
+    #[test]
+    fn prehash_above_threshold() {
+        let len = PREHASH_THRESHOLD + 1000;
+
+        assert_eq!(kdf_input("a".repeat(len).as_bytes()).len(), 64);
+        roundtrip(len);
+    }
+}
@@ -0,0 +1,258 @@
+//! Named Argon2 configurations.
+
+use crate::{HashError, HashTarget, Hashed, Password, Plain};
+#[cfg(feature = "test-util")]
+use argon2::password_hash::SaltString;
+use argon2::{password_hash::Salt, Algorithm, Argon2, Params, Version};
+
+/// Salt used by `hash_with_fixed_salt`, the Base64 encoding of `tag_password_salt`.
+#[cfg(feature = "deterministic-salt")]
+const FIXED_SALT: &str = "dGFnX3Bhc3N3b3JkX3NhbHQ";
+
+/// HKDF salt used by `hash_deterministic_for_tests` to derive salts from passwords.
+#[cfg(feature = "test-util")]
+const TEST_UTIL_HKDF_SALT: &[u8] = b"tag_password test-util salt";
+
+/// Named Argon2 configuration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Argon2Profile {
+    /// The default Argon2id parameters of the `argon2` crate, suitable for production.
+    #[default]
+    Default,
+    /// Argon2id with the minimal memory and time costs, keeping tests and CI fast.
+    ///
+    /// This profile offers no meaningful resistance to brute-force attacks and must never be
+    /// used to hash real passwords.
+    Testing,
+}
+
+impl Argon2Profile {
+    /// Retrieves the Argon2 parameters of the profile.
+    pub fn params(&self) -> Params {
+        match self {
+            Argon2Profile::Default => Params::default(),
+            Argon2Profile::Testing => Params::new(Params::MIN_M_COST, Params::MIN_T_COST, 1, None)
+                .expect("Minimal Argon2 parameters are valid."),
+        }
+    }
+
+    /// Builds the Argon2id configuration of the profile.
+    pub fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params())
+    }
+
+    /// Builds the `HashTarget` of the profile, to check stored hashes against it.
+    pub fn target(&self) -> HashTarget {
+        HashTarget::new(Algorithm::Argon2id, Version::V0x13, self.params())
+    }
+}
+
+/// Builds an Argon2id configuration from environment variables, falling back to the defaults.
+///
+/// The parameters are read from the following variables:
+///
+/// - `ARGON2_MEMORY`: The memory cost, in KiB.
+/// - `ARGON2_ITERATIONS`: The number of iterations.
+/// - `ARGON2_PARALLELISM`: The degree of parallelism.
+///
+/// Each variable that is unset or not a valid number falls back to the default of the `argon2`
+/// crate, as with `Argon2Profile::Default`. If the resulting combination is rejected by
+/// `Params::new`, such as a memory cost below 8 KiB per lane, every parameter falls back to
+/// the defaults, so a misconfigured environment never weakens hashing below them.
+///
+/// Returns the Argon2id configuration.
+pub fn argon2_from_env_or_default() -> Argon2<'static> {
+    let params = params_from(|name| std::env::var(name).ok());
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+}
+
+/// Builds Argon2 parameters from the values returned by `lookup` for each variable name.
+fn params_from(lookup: impl Fn(&str) -> Option<String>) -> Params {
+    let value = |name, default| {
+        lookup(name)
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(default)
+    };
+    Params::new(
+        value("ARGON2_MEMORY", Params::DEFAULT_M_COST),
+        value("ARGON2_ITERATIONS", Params::DEFAULT_T_COST),
+        value("ARGON2_PARALLELISM", Params::DEFAULT_P_COST),
+        None,
+    )
+    .unwrap_or_default()
+}
+
+impl Password<Plain> {
+    /// Hashes the password using the `Argon2Profile::Testing` configuration.
+    ///
+    /// - `salt`: A salt value used for hashing.
+    ///
+    /// This is intended only for tests and CI, where hashing with production parameters is
+    /// slow. The resulting hashes are trivial to brute-force and must never be stored for real
+    /// passwords. Hashes produced this way verify with `verify` like any other hash.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    pub fn hash_fast<'a>(&self, salt: impl Into<Salt<'a>>) -> Result<Password<Hashed>, HashError> {
+        self.hash_shared(&Argon2Profile::Testing.argon2(), salt)
+    }
+
+    /// Hashes the password with a fixed, publicly known salt.
+    ///
+    /// - `argon2`: An optional `Argon2` configuration.
+    ///
+    /// This is intended only for snapshot tests, where random salts make serialized output
+    /// nondeterministic. Equal passwords always produce equal hashes, which defeats the purpose
+    /// of salting, so the `deterministic-salt` feature must never be enabled outside of tests.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "deterministic-salt")]
+    pub fn hash_with_fixed_salt(
+        &self,
+        argon2: Option<Argon2>,
+    ) -> Result<Password<Hashed>, HashError> {
+        self.hash(argon2, Salt::from_b64(FIXED_SALT)?)
+    }
+
+    /// Hashes the password with a salt derived from the password itself. **Insecure, for tests
+    /// only.**
+    ///
+    /// The salt is derived with HKDF-SHA256 from the password and a fixed, public key, and the
+    /// password is hashed with the `Argon2Profile::Testing` configuration, so the same input
+    /// always yields the same hash across calls and runs. This is meant for stable test vectors
+    /// in integration tests. Anyone can recompute these hashes and precompute tables for them:
+    /// the `test-util` feature must never be enabled in production builds, for example by only
+    /// enabling it in `dev-dependencies`.
+    ///
+    /// Produces a result containing a new `Password` instance with the hashed value if successful.
+    /// If the password is empty or too long, or hashing fails, a `HashError` is returned.
+    #[cfg(feature = "test-util")]
+    pub fn hash_deterministic_for_tests(&self) -> Result<Password<Hashed>, HashError> {
+        let mut salt = [0u8; 16];
+        hkdf::Hkdf::<sha2::Sha256>::new(Some(TEST_UTIL_HKDF_SALT), self.as_bytes())
+            .expand(b"salt", &mut salt)
+            .expect("16 bytes is a valid HKDF-SHA256 output length.");
+        self.hash_fast(&SaltString::encode_b64(&salt)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{argon2_from_env_or_default, params_from, Argon2Profile};
+    use crate::{Password, Plain};
+    use argon2::{password_hash::SaltString, Params};
+
+    #[test]
+    fn testing_profile_uses_minimal_costs() {
+        let params = Argon2Profile::Testing.params();
+
+        assert_eq!(params.m_cost(), Params::MIN_M_COST);
+        assert_eq!(params.t_cost(), Params::MIN_T_COST);
+        assert_eq!(Argon2Profile::default().params(), Params::default());
+    }
+
+    #[test]
+    fn hash_fast_roundtrip() {
+        let plain_password: Password<Plain> = Password::new("Password");
+        let salt = SaltString::encode_b64(b"tag_password_salt").expect("Salt should be valid.");
+
+        let hashed_password = plain_password
+            .hash_fast(&salt)
+            .expect("Argon2 encoding should not fail.");
+
+        assert!(hashed_password.starts_with("$argon2id$v=19$m=8,t=1,p=1$"));
+        assert_eq!(
+            hashed_password.needs_rehash(&Argon2Profile::Testing.target()),
+            Ok(false)
+        );
+        assert_eq!(
+            hashed_password.needs_rehash(&Argon2Profile::Default.target()),
+            Ok(true)
+        );
+        hashed_password
+            .verify(None, plain_password)
+            .expect("Argon2 encoded password verification should not fail.");
+    }
+
+    #[test]
+    #[cfg(feature = "deterministic-salt")]
+    fn hash_with_fixed_salt_is_deterministic() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let first = plain_password
+            .hash_with_fixed_salt(Some(Argon2Profile::Testing.argon2()))
+            .expect("Argon2 encoding should not fail.");
+        let second = plain_password
+            .hash_with_fixed_salt(Some(Argon2Profile::Testing.argon2()))
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(first, second);
+        assert_eq!(first.raw(), crate::TEST_HASH);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn hash_deterministic_for_tests_is_stable() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let first = plain_password
+            .hash_deterministic_for_tests()
+            .expect("Argon2 encoding should not fail.");
+        let second = plain_password
+            .hash_deterministic_for_tests()
+            .expect("Argon2 encoding should not fail.");
+        let other = Password::<Plain>::new("Other")
+            .hash_deterministic_for_tests()
+            .expect("Argon2 encoding should not fail.");
+
+        assert_eq!(first, second);
+        assert_ne!(first.raw().split('$').nth(4), other.raw().split('$').nth(4));
+        assert!(first.matches(&plain_password));
+    }
+
+    #[test]
+    fn argon2_reads_environment() {
+        std::env::set_var("ARGON2_MEMORY", "32768");
+        std::env::set_var("ARGON2_ITERATIONS", "3");
+        std::env::set_var("ARGON2_PARALLELISM", "2");
+        let configured = argon2_from_env_or_default();
+        std::env::remove_var("ARGON2_MEMORY");
+        std::env::remove_var("ARGON2_ITERATIONS");
+        std::env::remove_var("ARGON2_PARALLELISM");
+        let params = configured.params();
+
+        assert_eq!(
+            (params.m_cost(), params.t_cost(), params.p_cost()),
+            (32768, 3, 2)
+        );
+        assert_eq!(argon2_from_env_or_default().params(), &Params::default());
+    }
+
+    #[test]
+    fn argon2_environment_falls_back_to_defaults() {
+        let params = params_from(|name| match name {
+            "ARGON2_MEMORY" => Some("lots".to_owned()),
+            "ARGON2_ITERATIONS" => Some(" 4 ".to_owned()),
+            _ => None,
+        });
+
+        assert_eq!(
+            (params.m_cost(), params.t_cost(), params.p_cost()),
+            (Params::DEFAULT_M_COST, 4, Params::DEFAULT_P_COST)
+        );
+        assert_eq!(
+            params_from(|name| (name == "ARGON2_ITERATIONS").then(|| "0".to_owned())),
+            Params::default()
+        );
+        assert_eq!(
+            params_from(|name| match name {
+                "ARGON2_MEMORY" => Some("8".to_owned()),
+                "ARGON2_PARALLELISM" => Some("2".to_owned()),
+                _ => None,
+            }),
+            Params::default()
+        );
+        assert_eq!(params_from(|_| None), Params::default());
+    }
+}
@@ -0,0 +1,273 @@
+//! Raw key derivation for digests stored without PHC metadata.
+
+use crate::{
+    check_kdf_input, check_memory, check_verify_input, constant_time_eq, prehash, HashError,
+    HashTarget, Hashed, Password, Plain, VerifyError,
+};
+use argon2::{Argon2, AssociatedData, KeyId, ParamsBuilder};
+use std::io::{self, Write};
+
+/// Size of the chunks written by `derive_key_to_writer`.
+const WRITE_CHUNK_LEN: usize = 4096;
+
+/// Label prefixing the associated data of session key derivations, separating them from
+/// other derivations sharing the same salt.
+const SESSION_KEY_LABEL: &[u8] = b"session:";
+
+impl Password<Plain> {
+    /// Derives a raw Argon2 key from the password.
+    ///
+    /// - `argon2`: The `Argon2` configuration, typically rebuilt from parameters stored in
+    ///   configuration.
+    /// - `salt`: The raw salt bytes.
+    /// - `output_len`: The length of the derived key in bytes.
+    ///
+    /// Unlike `hash`, the result carries no algorithm, parameters or salt, which must be stored
+    /// separately to verify it with `Password::<Hashed>::verify_raw`.
+    ///
+    /// Produces a result containing the derived key if successful. If the password is empty or
+    /// too long, or the parameters, salt or output length are invalid, a `HashError` is
+    /// returned.
+    pub fn derive_key(
+        &self,
+        argon2: &Argon2,
+        salt: &[u8],
+        output_len: usize,
+    ) -> Result<Vec<u8>, HashError> {
+        let input = prehash::kdf_input(self.as_bytes());
+        check_kdf_input(&input)?;
+        check_memory(argon2.params())?;
+
+        let mut key = vec![0u8; output_len];
+        argon2
+            .hash_password_into(&input, salt, &mut key)
+            .map_err(|error| HashError::from(argon2::password_hash::Error::from(error)))?;
+        Ok(key)
+    }
+
+    /// Derives a 32-byte session key from the password, bound to the provided context.
+    ///
+    /// - `target`: The Argon2 variant, version and parameters.
+    /// - `salt`: The raw salt bytes, which may be the salt of the stored hash.
+    /// - `context`: A value binding the key to its purpose, such as `b"session encryption"`, of
+    ///   at most 24 bytes.
+    ///
+    /// The salt is used as is, and a fixed label followed by the context is passed to Argon2 as
+    /// its associated data, so the session key differs from the digest of a stored hash using
+    /// the same salt, and keys for different contexts are unrelated. The same password, salt
+    /// and context always yield the same key. Argon2 limits associated data to 32 bytes, which
+    /// bounds the length of the context.
+    ///
+    /// Produces a result containing the session key if successful. If the password is empty or
+    /// too long, or the parameters, salt or context are invalid, a `HashError` is returned.
+    pub fn derive_session_key(
+        &self,
+        target: &HashTarget,
+        salt: &[u8],
+        context: &[u8],
+    ) -> Result<[u8; 32], HashError> {
+        let invalid_params = |error: argon2::Error| HashError::InvalidParams(error.into());
+        let mut data = Vec::with_capacity(SESSION_KEY_LABEL.len() + context.len());
+        data.extend_from_slice(SESSION_KEY_LABEL);
+        data.extend_from_slice(context);
+
+        let params = ParamsBuilder::new()
+            .m_cost(target.params.m_cost())
+            .t_cost(target.params.t_cost())
+            .p_cost(target.params.p_cost())
+            .keyid(KeyId::new(target.params.keyid()).map_err(invalid_params)?)
+            .data(AssociatedData::new(&data).map_err(invalid_params)?)
+            .build()
+            .map_err(invalid_params)?;
+        let argon2 = Argon2::new(target.algorithm, target.version, params);
+
+        let input = prehash::kdf_input(self.as_bytes());
+        check_kdf_input(&input)?;
+        check_memory(argon2.params())?;
+
+        let mut key = [0u8; 32];
+        argon2
+            .hash_password_into(&input, salt, &mut key)
+            .map_err(|error| HashError::from(argon2::password_hash::Error::from(error)))?;
+        Ok(key)
+    }
+
+    /// Derives a raw Argon2 key from the password and writes it into the provided sink.
+    ///
+    /// - `argon2`: The `Argon2` configuration.
+    /// - `salt`: The raw salt bytes.
+    /// - `output_len`: The length of the derived key in bytes.
+    /// - `writer`: The sink the key is written into, such as a file.
+    ///
+    /// The written bytes are the same as those returned by `derive_key`. Argon2 computes its
+    /// whole output in a single pass, so the key is still derived into a buffer of
+    /// `output_len` bytes before being written in chunks.
+    ///
+    /// Produces a result indicating whether the key was written. If key derivation fails, an
+    /// `io::Error` of kind `InvalidInput` wrapping the `HashError` is returned, and errors of
+    /// the writer are returned as is.
+    pub fn derive_key_to_writer(
+        &self,
+        argon2: &Argon2,
+        salt: &[u8],
+        output_len: usize,
+        writer: &mut impl Write,
+    ) -> io::Result<()> {
+        let key = self
+            .derive_key(argon2, salt, output_len)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        for chunk in key.chunks(WRITE_CHUNK_LEN) {
+            writer.write_all(chunk)?;
+        }
+        writer.flush()
+    }
+}
+
+impl Password<Hashed> {
+    /// Verifies a plain text password against a raw digest, without relying on PHC metadata.
+    ///
+    /// - `argon2`: The `Argon2` configuration the digest was derived with.
+    /// - `salt`: The raw salt bytes the digest was derived with.
+    /// - `expected_digest`: The stored digest, such as one produced by `derive_key`.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// This is meant for legacy rows storing only the digest, with the parameters and salt kept
+    /// elsewhere. A key of the same length as `expected_digest` is derived and compared in
+    /// constant time. The input length and the memory required by `argon2` are checked as in
+    /// `derive_key`.
+    ///
+    /// This is an associated function rather than a method taking `&self`: such rows hold no
+    /// PHC string to build a `Password<Hashed>` from, and everything needed for verification is
+    /// passed explicitly, so a receiver would go unused.
+    ///
+    /// Returns `Ok(true)` if the passwords match and `Ok(false)` if they do not. Otherwise,
+    /// `VerifyError::TooLong` is returned if the password is longer than
+    /// `MAX_HASH_INPUT_LEN`, `VerifyError::AllocationFailed` if the memory required by `argon2`
+    /// cannot be allocated, and `VerifyError::Backend` if the parameters, salt or digest length
+    /// are invalid.
+    pub fn verify_raw(
+        argon2: &Argon2,
+        salt: &[u8],
+        expected_digest: &[u8],
+        plain: &Password<Plain>,
+    ) -> Result<bool, VerifyError> {
+        let input = prehash::kdf_input(plain.as_bytes());
+        check_verify_input(&input, Some(argon2.params()))?;
+
+        let mut key = vec![0u8; expected_digest.len()];
+        argon2
+            .hash_password_into(&input, salt, &mut key)
+            .map_err(|error| VerifyError::Backend(error.into()))?;
+        Ok(constant_time_eq(&key, expected_digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Argon2Profile, HashError, Hashed, Password, Plain, VerifyError, TEST_HASH};
+
+    #[test]
+    fn derive_key_and_verify_raw_roundtrip() {
+        let argon2 = Argon2Profile::Testing.argon2();
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let digest = plain_password
+            .derive_key(&argon2, b"tag_password_salt", 32)
+            .expect("Key derivation should not fail.");
+
+        assert_eq!(
+            Password::<Hashed>::new_unchecked(TEST_HASH).digest(),
+            Ok(digest.clone())
+        );
+        assert_eq!(
+            Password::<Hashed>::verify_raw(&argon2, b"tag_password_salt", &digest, &plain_password),
+            Ok(true)
+        );
+        assert_eq!(
+            Password::<Hashed>::verify_raw(
+                &argon2,
+                b"tag_password_salt",
+                &digest,
+                &Password::new("Wrong")
+            ),
+            Ok(false)
+        );
+        assert_eq!(
+            Password::<Hashed>::verify_raw(&argon2, b"other_salt", &digest, &plain_password),
+            Ok(false)
+        );
+        assert!(matches!(
+            Password::<Hashed>::verify_raw(&argon2, b"salt", &digest, &plain_password),
+            Err(VerifyError::Backend(_))
+        ));
+        assert_eq!(
+            Password::<Plain>::new("").derive_key(&argon2, b"tag_password_salt", 32),
+            Err(HashError::EmptyInput)
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "prehash-long"))]
+    fn verify_raw_rejects_too_long_input() {
+        use crate::MAX_HASH_INPUT_LEN;
+
+        assert_eq!(
+            Password::<Hashed>::verify_raw(
+                &Argon2Profile::Testing.argon2(),
+                b"tag_password_salt",
+                &[0; 32],
+                &Password::new("a".repeat(MAX_HASH_INPUT_LEN + 1))
+            ),
+            Err(VerifyError::TooLong {
+                max: MAX_HASH_INPUT_LEN
+            })
+        );
+    }
+
+    #[test]
+    fn derive_key_into_writer() {
+        let argon2 = Argon2Profile::Testing.argon2();
+        let plain_password: Password<Plain> = Password::new("Password");
+        let mut buffer = Vec::new();
+
+        plain_password
+            .derive_key_to_writer(&argon2, b"tag_password_salt", 10_000, &mut buffer)
+            .expect("Key derivation should not fail.");
+
+        assert_eq!(
+            plain_password.derive_key(&argon2, b"tag_password_salt", 10_000),
+            Ok(buffer)
+        );
+        assert_eq!(
+            Password::<Plain>::new("")
+                .derive_key_to_writer(&argon2, b"tag_password_salt", 32, &mut Vec::new())
+                .map_err(|error| error.kind()),
+            Err(std::io::ErrorKind::InvalidInput)
+        );
+    }
+
+    #[test]
+    fn session_keys_are_bound_to_context() {
+        let target = Argon2Profile::Testing.target();
+        let plain_password: Password<Plain> = Password::new("Password");
+        let derive = |context: &[u8]| {
+            plain_password
+                .derive_session_key(&target, b"tag_password_salt", context)
+                .expect("Key derivation should not fail.")
+        };
+
+        assert_eq!(derive(b"session"), derive(b"session"));
+        assert_ne!(derive(b"session"), derive(b"export"));
+        assert_ne!(derive(b""), derive(b"session"));
+        for context in [&b""[..], b"session"] {
+            assert_ne!(
+                Password::<Hashed>::new_unchecked(TEST_HASH).digest(),
+                Ok(derive(context).to_vec())
+            );
+        }
+        assert!(matches!(
+            plain_password.derive_session_key(&target, b"tag_password_salt", &[0; 25]),
+            Err(HashError::InvalidParams(_))
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+//! Registry dispatching verification to a verifier based on the PHC algorithm identifier.
+
+use crate::{Hashed, Password, Plain};
+use argon2::{
+    password_hash::{Error, Ident, Result},
+    Algorithm, Argon2, PasswordHash, PasswordVerifier,
+};
+use std::collections::HashMap;
+
+/// Collection of password verifiers keyed by their PHC algorithm identifier.
+///
+/// A registry lets `verify_with_registry` dispatch a hashed password to the verifier matching
+/// its identifier, including verifiers for algorithms this crate does not ship.
+#[derive(Default)]
+pub struct VerifierRegistry {
+    verifiers: HashMap<String, Box<dyn PasswordVerifier + Send + Sync>>,
+}
+
+impl VerifierRegistry {
+    /// Creates a new, empty `VerifierRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `VerifierRegistry` with the default Argon2 configuration registered for
+    /// the `argon2d`, `argon2i` and `argon2id` identifiers.
+    pub fn with_argon2() -> Self {
+        let mut registry = Self::new();
+        for algorithm in [Algorithm::Argon2d, Algorithm::Argon2i, Algorithm::Argon2id] {
+            registry.register(algorithm.ident(), Box::new(Argon2::default()));
+        }
+        registry
+    }
+
+    /// Registers a verifier for the provided algorithm identifier.
+    ///
+    /// - `ident`: The PHC algorithm identifier handled by the verifier.
+    /// - `verifier`: The verifier used for hashes with this identifier.
+    ///
+    /// Any verifier previously registered for the identifier is replaced.
+    pub fn register(
+        &mut self,
+        ident: Ident<'_>,
+        verifier: Box<dyn PasswordVerifier + Send + Sync>,
+    ) -> &mut Self {
+        self.verifiers.insert(ident.as_str().to_owned(), verifier);
+        self
+    }
+
+    /// Retrieves the verifier registered for the provided algorithm identifier.
+    pub fn get(&self, ident: Ident<'_>) -> Option<&(dyn PasswordVerifier + Send + Sync)> {
+        self.verifiers.get(ident.as_str()).map(Box::as_ref)
+    }
+}
+
+impl Password<Hashed> {
+    /// Verifies the hashed password using the verifier registered for its algorithm.
+    ///
+    /// - `registry`: The registry containing the available verifiers.
+    /// - `plain`: A plain text password used for verification.
+    ///
+    /// Returns a result indicating success or failure of the verification process.
+    /// If successful, `Ok(())` is returned. If no verifier is registered for the algorithm,
+    /// `Error::Algorithm` is returned, and other failures are reported as for `verify`.
+    pub fn verify_with_registry(
+        &self,
+        registry: &VerifierRegistry,
+        plain: impl Into<Password<Plain>>,
+    ) -> Result<()> {
+        let hash = PasswordHash::new(&self.1)?;
+        registry
+            .get(hash.algorithm)
+            .ok_or(Error::Algorithm)
+            .and_then(|verifier| {
+                crate::verify_kdf_input_phc(
+                    verifier,
+                    &crate::prehash::kdf_input(plain.into().as_bytes()),
+                    &hash,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VerifierRegistry;
+    use crate::{Hashed, Password};
+    use argon2::{
+        password_hash::{Error, Ident, Result},
+        PasswordHash, PasswordVerifier,
+    };
+
+    /// Verifier storing the password itself as the hash output, for testing dispatch only.
+    struct IdentityVerifier;
+
+    impl PasswordVerifier for IdentityVerifier {
+        fn verify_password(&self, password: &[u8], hash: &PasswordHash<'_>) -> Result<()> {
+            match hash.hash {
+                Some(output) if output.as_bytes() == password => Ok(()),
+                _ => Err(Error::Password),
+            }
+        }
+    }
+
+    #[test]
+    fn dispatch_to_custom_verifier() {
+        let mut registry = VerifierRegistry::with_argon2();
+        registry.register(Ident::new_unwrap("identity"), Box::new(IdentityVerifier));
+
+        let hashed_password: Password<Hashed> =
+            Password::new_unchecked("$identity$c2FsdHNhbHQ$Y29ycmVjdCBob3JzZSBiYXR0ZXJ5");
+
+        assert!(hashed_password
+            .verify_with_registry(&registry, Password::new("correct horse battery"))
+            .is_ok());
+        assert_eq!(
+            hashed_password.verify_with_registry(&registry, Password::new("Password")),
+            Err(Error::Password)
+        );
+    }
+
+    #[test]
+    fn dispatch_to_argon2_and_unknown_algorithm() {
+        let registry = VerifierRegistry::with_argon2();
+        let hashed_password: Password<Hashed> = Password::new_unchecked(crate::TEST_HASH);
+
+        assert!(hashed_password
+            .verify_with_registry(&registry, Password::new("Password"))
+            .is_ok());
+        assert_eq!(
+            hashed_password
+                .verify_with_registry(&VerifierRegistry::new(), Password::new("Password")),
+            Err(Error::Algorithm)
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Signed, time-limited tokens derived from plain text passwords.
+
+use crate::{keyed::to_hex, Password, Plain};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Domain separator keeping reset token signatures distinct from other uses of the key.
+const DOMAIN: &[u8] = b"tag_password reset token v1";
+
+impl Password<Plain> {
+    /// Derives a signed, time-limited token from the password, such as for password reset links.
+    ///
+    /// - `key`: The secret server key signing the token.
+    /// - `ttl`: How long the token stays valid.
+    ///
+    /// The token has the form `<expiry>.<signature>`, where `expiry` is a Unix timestamp in
+    /// seconds and `signature` is the hex-encoded HMAC-SHA256 of the expiry and the password.
+    /// It only contains URL-safe characters and does not reveal the password, which is needed
+    /// again to verify it with `verify_reset_token`.
+    ///
+    /// The token is not single use: it stays valid until it expires, however many times it is
+    /// verified, and is only invalidated earlier by changing the password it was derived from.
+    /// Tokens issued within the same second for the same password are identical. To stop a
+    /// leaked token from being replayed, record consumed tokens until their expiry and reject
+    /// them before calling `verify_reset_token`, and keep `ttl` short.
+    ///
+    /// Returns the token.
+    pub fn to_reset_token(&self, key: &[u8], ttl: Duration) -> String {
+        let expiry = now().saturating_add(ttl.as_secs());
+        let signature = mac(key, expiry, self).finalize().into_bytes();
+        format!("{expiry}.{}", to_hex(&signature))
+    }
+}
+
+/// Verifies a token produced by `to_reset_token`.
+///
+/// - `token`: The token to verify.
+/// - `key`: The secret server key the token was signed with.
+/// - `plain`: The plain text password the token was derived from.
+///
+/// The signature is compared in constant time. Verification is stateless, so a token verified
+/// once keeps verifying until it expires: see `Password::to_reset_token` for preventing
+/// replays.
+///
+/// Returns `true` if the token is well-formed, not expired and signed for the password with
+/// the key, and `false` otherwise.
+pub fn verify_reset_token(token: &str, key: &[u8], plain: &Password<Plain>) -> bool {
+    verify_reset_token_at(token, key, plain, now())
+}
+
+/// Verifies a token as `verify_reset_token` would at the provided Unix timestamp.
+fn verify_reset_token_at(token: &str, key: &[u8], plain: &Password<Plain>, now: u64) -> bool {
+    let Some((expiry, signature)) = token.split_once('.') else {
+        return false;
+    };
+    let (Ok(expiry), Some(signature)) = (expiry.parse::<u64>(), from_hex(signature)) else {
+        return false;
+    };
+    now <= expiry && mac(key, expiry, plain).verify_slice(&signature).is_ok()
+}
+
+/// Starts the HMAC-SHA256 of the expiry and password.
+fn mac(key: &[u8], expiry: u64, plain: &Password<Plain>) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length.");
+    mac.update(DOMAIN);
+    mac.update(&expiry.to_be_bytes());
+    mac.update(plain.as_bytes());
+    mac
+}
+
+/// Decodes lowercase hex, returning `None` for malformed input.
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 == 1 {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| {
+            let pair = value.get(index..index + 2)?;
+            match pair
+                .bytes()
+                .all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+            {
+                true => u8::from_str_radix(pair, 16).ok(),
+                false => None,
+            }
+        })
+        .collect()
+}
+
+/// Retrieves the current Unix timestamp in seconds.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify_reset_token, verify_reset_token_at};
+    use crate::{Password, Plain};
+    use std::time::Duration;
+
+    const KEY: &[u8] = b"tag_password_key";
+
+    #[test]
+    fn valid_reset_token() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let token = plain_password.to_reset_token(KEY, Duration::from_secs(3600));
+
+        assert!(token
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'.'));
+        assert!(!token.contains("Password"));
+        assert!(verify_reset_token(&token, KEY, &plain_password));
+        assert!(!verify_reset_token(&token, b"other_key", &plain_password));
+        assert!(!verify_reset_token(&token, KEY, &Password::new("Wrong")));
+    }
+
+    #[test]
+    fn expired_reset_token() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let token = plain_password.to_reset_token(KEY, Duration::from_secs(60));
+        let expiry: u64 = token
+            .split_once('.')
+            .and_then(|(expiry, _)| expiry.parse().ok())
+            .expect("Token should start with its expiry.");
+
+        assert!(verify_reset_token_at(&token, KEY, &plain_password, expiry));
+        assert!(!verify_reset_token_at(
+            &token,
+            KEY,
+            &plain_password,
+            expiry + 1
+        ));
+    }
+
+    #[test]
+    fn tampered_reset_token() {
+        let plain_password: Password<Plain> = Password::new("Password");
+
+        let token = plain_password.to_reset_token(KEY, Duration::from_secs(60));
+        let (expiry, signature) = token.split_once('.').expect("Token should contain a dot.");
+        let extended = format!(
+            "{}.{signature}",
+            expiry.parse::<u64>().expect("Expiry should be a number.") + 3600
+        );
+        let mut flipped = token.clone().into_bytes();
+        let last = flipped.last_mut().expect("Token should not be empty.");
+        *last = if *last == b'0' { b'1' } else { b'0' };
+
+        assert!(!verify_reset_token(&extended, KEY, &plain_password));
+        assert!(!verify_reset_token(
+            &String::from_utf8(flipped).expect("Token should be valid UTF-8."),
+            KEY,
+            &plain_password
+        ));
+        assert!(!verify_reset_token(expiry, KEY, &plain_password));
+        assert!(!verify_reset_token(
+            &format!("{expiry}.zz"),
+            KEY,
+            &plain_password
+        ));
+    }
+}
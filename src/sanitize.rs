@@ -0,0 +1,259 @@
+//! Normalization of plain text passwords copied from other sources.
+//!
+//! These transformations change the bytes that are hashed, so each one must be applied
+//! symmetrically: both when the password is set and whenever it is verified, with the same
+//! options. Otherwise a password set in one form never matches a submission in the other, such
+//! as a password set with uppercase characters against its case-folded form.
+
+use crate::{Password, Plain};
+#[cfg(feature = "unicode")]
+use caseless::Caseless;
+#[cfg(feature = "unicode")]
+use unicode_normalization::UnicodeNormalization;
+#[cfg(feature = "confusables")]
+use unicode_security::MixedScript;
+
+/// Unicode normalization form applied to plain text passwords.
+///
+/// The same password typed on different systems can be encoded with different code points,
+/// such as a precomposed `é` or an `e` followed by a combining accent. Normalizing passwords
+/// makes these encodings match.
+///
+/// NFC is usually the right choice for passwords: it only merges canonically equivalent
+/// sequences. The compatibility forms NFKC and NFKD additionally fold characters such as
+/// ligatures, full-width letters or superscripts into their plain equivalents, which alters
+/// characters the user deliberately typed and reduces the space of distinct passwords.
+#[cfg(feature = "unicode")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NormalizationForm {
+    /// Canonical composition.
+    #[default]
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Password<Plain> {
+    /// Removes null bytes and other control characters from the password.
+    ///
+    /// Passwords pasted from some sources carry stray null or control characters that are
+    /// invisible to the user and cause mismatches. Apply it [symmetrically](crate::sanitize). When anything was
+    /// removed, warn the user so they know which password was actually stored.
+    ///
+    /// Tabs and line breaks are control characters and are removed as well.
+    ///
+    /// Returns the sanitized password and `true` if any character was removed.
+    pub fn sanitize_control_chars(self) -> (Password<Plain>, bool) {
+        if !self.1.chars().any(char::is_control) {
+            return (self, false);
+        }
+        let sanitized: String = self.1.chars().filter(|c| !c.is_control()).collect();
+        (Password::new(sanitized), true)
+    }
+
+    /// Truncates the password to at most the provided number of bytes, for systems with hard
+    /// length limits.
+    ///
+    /// - `max_bytes`: The maximum length of the password, in bytes.
+    ///
+    /// When the limit falls inside a multibyte character, that character is removed entirely
+    /// rather than split, so the result may be shorter than `max_bytes`. Apply the same limit
+    /// [symmetrically](crate::sanitize).
+    ///
+    /// Returns the truncated password.
+    pub fn truncate_to(self, max_bytes: usize) -> Password<Plain> {
+        if self.1.len() <= max_bytes {
+            return self;
+        }
+        let end = (0..=max_bytes)
+            .rev()
+            .find(|&end| self.1.is_char_boundary(end))
+            .unwrap_or(0);
+        Password::new(&self.1[..end])
+    }
+
+    /// Converts the password to uppercase, for legacy systems that stored case-normalized
+    /// passwords.
+    ///
+    /// Every casing of the password is then accepted, which makes it easier to guess. Apply it
+    /// [symmetrically](crate::sanitize), and use `case_fold` instead for new case-insensitive policies.
+    ///
+    /// Returns the uppercase password.
+    pub fn to_uppercase(self) -> Password<Plain> {
+        Password::new(self.1.to_uppercase())
+    }
+
+    /// Converts the password to lowercase, for legacy systems that stored case-normalized
+    /// passwords.
+    ///
+    /// Every casing of the password is then accepted, which makes it easier to guess. Apply it
+    /// [symmetrically](crate::sanitize), and use `case_fold` instead for new case-insensitive policies.
+    ///
+    /// Returns the lowercase password.
+    pub fn to_lowercase(self) -> Password<Plain> {
+        Password::new(self.1.to_lowercase())
+    }
+
+    /// Normalizes the password to the provided Unicode normalization form.
+    ///
+    /// - `form`: The normalization form, typically `NormalizationForm::Nfc` for passwords.
+    ///
+    /// Apply the same form [symmetrically](crate::sanitize).
+    ///
+    /// Returns the normalized password.
+    #[cfg(feature = "unicode")]
+    pub fn normalize_form(self, form: NormalizationForm) -> Password<Plain> {
+        let normalized: String = match form {
+            NormalizationForm::Nfc => self.1.nfc().collect(),
+            NormalizationForm::Nfd => self.1.nfd().collect(),
+            NormalizationForm::Nfkc => self.1.nfkc().collect(),
+            NormalizationForm::Nfkd => self.1.nfkd().collect(),
+        };
+        Password::new(normalized)
+    }
+
+    /// Returns `true` if the password mixes letters of several scripts, such as Latin and
+    /// Cyrillic.
+    ///
+    /// Mixed scripts are a common sign of homoglyphs, such as a Cyrillic `а` standing in for a
+    /// Latin `a`. Digits, punctuation and other characters shared by all scripts are ignored.
+    #[cfg(feature = "confusables")]
+    pub fn contains_mixed_scripts(&self) -> bool {
+        !self.1.as_str().is_single_script()
+    }
+
+    /// Replaces characters that look alike with a common prototype, using the Unicode
+    /// confusables table.
+    ///
+    /// This maps homoglyphs, such as a Cyrillic `а` and a Latin `a`, to the same character, so
+    /// policies can compare look-alike passwords, for example against a list of banned
+    /// passwords. Confusable characters within a single script are mapped as well, such as
+    /// `1` and `l`, and the result is in NFD. It is meant for policy checks: hashing the
+    /// normalized password instead of the original one makes distinct passwords collide.
+    ///
+    /// Returns the normalized password.
+    #[cfg(feature = "confusables")]
+    pub fn normalize_confusables(self) -> Password<Plain> {
+        Password::new(unicode_security::skeleton(&self.1).collect::<String>())
+    }
+
+    /// Applies Unicode default case folding to the password, for case-insensitive passphrase
+    /// policies.
+    ///
+    /// Case folding maps characters that differ only by case to the same value, including
+    /// cases that lowercasing misses, such as `ß` and `ss`. This makes the password easier to
+    /// guess, since every casing of it is accepted, so only use it where case-insensitive
+    /// passwords are an explicit requirement. Apply it [symmetrically](crate::sanitize).
+    ///
+    /// Returns the case-folded password.
+    #[cfg(feature = "unicode")]
+    pub fn case_fold(self) -> Password<Plain> {
+        Password::new(self.1.chars().default_case_fold().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Password, Plain};
+
+    #[test]
+    fn sanitize_removes_control_chars() {
+        let (sanitized, removed) =
+            Password::<Plain>::new("\0Pass\u{7}word\r\n").sanitize_control_chars();
+
+        assert_eq!(sanitized.raw(), "Password");
+        assert!(removed);
+    }
+
+    #[test]
+    fn sanitize_keeps_clean_passwords() {
+        let (sanitized, removed) = Password::<Plain>::new(" Pass wörd ").sanitize_control_chars();
+
+        assert_eq!(sanitized.raw(), " Pass wörd ");
+        assert!(!removed);
+    }
+
+    #[test]
+    fn truncate_respects_char_boundaries() {
+        let truncate = |value: &str, max| Password::<Plain>::new(value).truncate_to(max);
+
+        assert_eq!(truncate("Password", 4).raw(), "Pass");
+        assert_eq!(truncate("Password", 64).raw(), "Password");
+        assert_eq!(truncate("pa\u{df}wort", 3).raw(), "pa");
+        assert_eq!(truncate("pa\u{df}wort", 4).raw(), "pa\u{df}");
+        assert_eq!(truncate("\u{1F511}key", 3).raw(), "");
+        assert_eq!(truncate("\u{1F511}key", 5).raw(), "\u{1F511}k");
+    }
+
+    #[test]
+    fn case_conversions() {
+        let mixed = Password::<Plain>::new("PaSs W\u{F6}rd");
+
+        assert_eq!(mixed.clone().to_uppercase().raw(), "PASS W\u{D6}RD");
+        assert_eq!(mixed.clone().to_lowercase().raw(), "pass w\u{F6}rd");
+        assert_eq!(
+            mixed.clone().to_uppercase().to_lowercase(),
+            mixed.to_lowercase()
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn normalization_forms() {
+        use super::NormalizationForm;
+
+        let normalize = |value: &str, form| Password::<Plain>::new(value).normalize_form(form);
+
+        assert_eq!(
+            normalize("\u{FB01}le", NormalizationForm::Nfc).raw(),
+            "\u{FB01}le"
+        );
+        assert_eq!(
+            normalize("\u{FB01}le", NormalizationForm::Nfkc).raw(),
+            "file"
+        );
+        assert_eq!(
+            normalize("e\u{301}", NormalizationForm::Nfc).raw(),
+            "\u{E9}"
+        );
+        assert_eq!(
+            normalize("\u{E9}", NormalizationForm::Nfd).raw(),
+            "e\u{301}"
+        );
+        assert_eq!(normalize("\u{FB01}", NormalizationForm::Nfkd).raw(), "fi");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn case_fold_ignores_case() {
+        let fold = |value: &str| Password::<Plain>::new(value).case_fold();
+
+        assert_eq!(fold("Hello"), fold("hello"));
+        assert_eq!(fold("Hello").raw(), "hello");
+        assert_eq!(fold("Stra\u{DF}e").raw(), "strasse");
+        assert_ne!(fold("Hello"), fold("Hallo"));
+    }
+
+    #[cfg(feature = "confusables")]
+    #[test]
+    fn mixed_scripts_detection() {
+        let spoofed = Password::<Plain>::new("p\u{430}ssword");
+        let latin = Password::<Plain>::new("Password");
+
+        assert!(spoofed.contains_mixed_scripts());
+        assert!(!latin.contains_mixed_scripts());
+        assert!(
+            !Password::<Plain>::new("\u{43F}\u{430}\u{440}\u{43E}\u{43B}\u{44C}")
+                .contains_mixed_scripts()
+        );
+        assert_eq!(
+            spoofed.normalize_confusables(),
+            Password::<Plain>::new("password").normalize_confusables()
+        );
+        assert_eq!(latin.normalize_confusables().raw(), "Password");
+    }
+}
@@ -0,0 +1,40 @@
+//! Zeroizing password buffers once they are no longer needed.
+
+use crate::Password;
+use zeroize::Zeroize;
+
+impl<T: crate::sealed::Marker + ?Sized> Password<T> {
+    /// Clones the password and zeroizes the original in one step.
+    ///
+    /// This is meant for handoff patterns where the original value should not persist, such as
+    /// moving a password out of a long-lived request struct. The original buffer is overwritten
+    /// with zeros and left empty, whereas a plain `clone` leaves both copies in memory.
+    ///
+    /// Returns a new `Password` holding the value.
+    pub fn clone_and_scrub(&mut self) -> Self {
+        let clone = self.clone();
+        self.1.zeroize();
+        self.2 = None;
+        clone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Password, Plain};
+
+    #[test]
+    fn clone_and_scrub_clears_original() {
+        let mut original: Password<Plain> = Password::new("Password");
+        let capacity = original.1.capacity();
+
+        let clone = original.clone_and_scrub();
+
+        assert_eq!(clone.raw(), "Password");
+        assert!(original.raw().is_empty());
+        assert_eq!(original.1.capacity(), capacity);
+        // SAFETY: The bytes are within the capacity of the buffer, which zeroize keeps allocated.
+        let buffer = unsafe { std::slice::from_raw_parts(original.1.as_ptr(), capacity) };
+        assert!(buffer.iter().all(|&byte| byte == 0));
+    }
+}
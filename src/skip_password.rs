@@ -0,0 +1,69 @@
+//! Serde helpers omitting password fields from serialized output.
+//!
+//! Annotate a password field with
+//! `#[serde(skip_serializing_if = "tag_password::skip_password::should_skip")]` to keep it
+//! out of serialized payloads, such as public API responses. The field is only written when
+//! serialization happens inside `exposed`, for example when persisting the value internally.
+//!
+//! ```rust
+//! use serde::Serialize;
+//! use tag_password::{skip_password, Hashed, Password};
+//!
+//! #[derive(Serialize)]
+//! struct User {
+//!     name: String,
+//!     #[serde(skip_serializing_if = "skip_password::should_skip")]
+//!     password: Password<Hashed>,
+//! }
+//!
+//! let user = User {
+//!     name: "admin".into(),
+//!     password: serde_json::from_str(
+//!         r#""$argon2id$v=19$m=8,t=1,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$VSGjAZoWLd8Ds9tkbDEx49KABqrdxsMoISbYU31PMbM""#,
+//!     )
+//!     .unwrap(),
+//! };
+//!
+//! let public = serde_json::to_string(&user).unwrap();
+//! let internal = skip_password::exposed(|| serde_json::to_string(&user)).unwrap();
+//! assert!(!public.contains("password"));
+//! assert!(internal.contains("password"));
+//! ```
+
+use crate::Password;
+use std::cell::Cell;
+
+thread_local! {
+    static EXPOSED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the previous exposure state when dropped.
+struct ExposedGuard(bool);
+
+impl Drop for ExposedGuard {
+    fn drop(&mut self) {
+        EXPOSED.with(|exposed| exposed.set(self.0));
+    }
+}
+
+/// Returns whether password fields are currently serialized on this thread.
+///
+/// This is `false` by default and `true` only within `exposed`.
+pub fn should_serialize() -> bool {
+    EXPOSED.with(Cell::get)
+}
+
+/// Predicate for `skip_serializing_if` omitting the password unless serialization is exposed.
+pub fn should_skip<T: ?Sized>(_: &Password<T>) -> bool {
+    !should_serialize()
+}
+
+/// Runs the provided closure with password fields serialized on the current thread.
+///
+/// - `f`: The closure performing the serialization.
+///
+/// Returns the value produced by the closure.
+pub fn exposed<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = ExposedGuard(EXPOSED.with(|exposed| exposed.replace(true)));
+    f()
+}
@@ -0,0 +1,77 @@
+//! Hashing streams of plain text passwords in async pipelines.
+
+use crate::{generate_salt, HashError, Hashed, Password, Plain};
+use argon2::Argon2;
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+
+impl Password<Plain> {
+    /// Hashes a stream of plain text passwords with bounded concurrency.
+    ///
+    /// - `passwords`: The plain text passwords to hash.
+    /// - `argon2`: An optional `Argon2` configuration, shared by all hashing tasks.
+    /// - `concurrency`: The maximum number of passwords hashed at the same time, at least 1.
+    ///
+    /// Each password is hashed with a new random salt on Tokio's blocking thread pool, so the
+    /// returned stream must be polled within a Tokio runtime. Results are produced in completion
+    /// order, which may differ from the order of `passwords`. A panic while hashing is resumed
+    /// when polling the stream.
+    ///
+    /// Returns a stream of results, one per input password, holding the hashed password or a
+    /// `HashError`.
+    pub fn to_hashed_batch_stream<S>(
+        passwords: S,
+        argon2: Option<Argon2<'static>>,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<Password<Hashed>, HashError>>
+    where
+        S: Stream<Item = Password<Plain>>,
+    {
+        let argon2 = Arc::new(argon2.unwrap_or_default());
+        passwords
+            .map(move |plain| {
+                let argon2 = Arc::clone(&argon2);
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        plain.hash_shared(&argon2, &generate_salt())
+                    })
+                    .await
+                    .unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()))
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Argon2Profile, Password, Plain};
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn hashed_batch_stream_verifies() {
+        let plain_passwords: Vec<Password<Plain>> = (0..8)
+            .map(|index| Password::new(format!("Password{index}")))
+            .collect();
+
+        let hashed_passwords: Vec<_> = Password::to_hashed_batch_stream(
+            stream::iter(plain_passwords.clone()),
+            Some(Argon2Profile::Testing.argon2()),
+            3,
+        )
+        .collect()
+        .await;
+
+        assert_eq!(hashed_passwords.len(), plain_passwords.len());
+        for hashed_password in hashed_passwords {
+            let hashed_password = hashed_password.expect("Argon2 encoding should not fail.");
+            assert_eq!(
+                plain_passwords
+                    .iter()
+                    .filter(|plain| hashed_password.matches(plain))
+                    .count(),
+                1
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Reading plain text passwords from a terminal without echo.
+
+use crate::{Password, Plain};
+use std::io::{self, BufRead, Write};
+
+impl Password<Plain> {
+    /// Prompts for a password on the terminal without echoing the input.
+    ///
+    /// - `prompt`: The text written to the terminal before reading, such as `"Password: "`.
+    ///
+    /// The trailing line ending is removed, while any other whitespace is kept as typed.
+    ///
+    /// Returns the password read from the terminal, or an `io::Error` if no terminal is
+    /// available or reading fails.
+    pub fn prompt(prompt: &str) -> io::Result<Self> {
+        rpassword::prompt_password(prompt).map(Password::new)
+    }
+
+    /// Prompts for a password using the provided reader and writer instead of the terminal.
+    ///
+    /// - `reader`: The source of the password, read up to the first line ending.
+    /// - `writer`: The destination of the prompt.
+    /// - `prompt`: The text written before reading.
+    ///
+    /// This behaves like `prompt` and is useful for tests and non-interactive input, such as a
+    /// password piped through standard input. Input ending without a line ending is accepted.
+    ///
+    /// Returns the password read from `reader`, or an `io::Error` if writing or reading fails.
+    pub fn prompt_from(
+        reader: &mut impl BufRead,
+        writer: &mut impl Write,
+        prompt: &str,
+    ) -> io::Result<Self> {
+        writer.write_all(prompt.as_bytes())?;
+        writer.flush()?;
+
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        Ok(Password::new(strip_line_ending(line)))
+    }
+}
+
+/// Removes a single trailing `\n` or `\r\n` from the line.
+fn strip_line_ending(mut line: String) -> String {
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_line_ending;
+    use crate::{Password, Plain};
+    use std::io::Cursor;
+
+    #[test]
+    #[ignore = "requires an interactive terminal"]
+    fn prompt_reads_from_terminal() {
+        let plain_password =
+            Password::<Plain>::prompt("Password: ").expect("Reading should not fail.");
+
+        assert!(!plain_password.raw().ends_with('\n'));
+    }
+
+    #[test]
+    fn prompt_from_removes_line_ending() {
+        let mut output = Vec::new();
+
+        let plain_password = Password::<Plain>::prompt_from(
+            &mut Cursor::new(" hunter2 \r\nnext\n"),
+            &mut output,
+            "Password: ",
+        )
+        .expect("Reading should not fail.");
+
+        assert_eq!(plain_password.raw(), " hunter2 ");
+        assert_eq!(output, b"Password: ");
+    }
+
+    #[test]
+    fn strip_line_ending_removes_one_line_ending() {
+        assert_eq!(strip_line_ending("hunter2\n".to_owned()), "hunter2");
+        assert_eq!(strip_line_ending("hunter2\r\n".to_owned()), "hunter2");
+        assert_eq!(strip_line_ending("hunter2\n\n".to_owned()), "hunter2\n");
+        assert_eq!(strip_line_ending("hunter2\r".to_owned()), "hunter2\r");
+        assert_eq!(strip_line_ending("hunter2".to_owned()), "hunter2");
+    }
+}
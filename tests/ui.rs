@@ -0,0 +1,9 @@
+//! Compile tests checking that hashed passwords cannot be fabricated from arbitrary data.
+
+#![cfg(feature = "argon2")]
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,5 @@
+use tag_password::{Password, Plain};
+
+fn main() {
+    let _hashed_password = unsafe { Password::<Plain>::new("not a hash").as_hashed() };
+}
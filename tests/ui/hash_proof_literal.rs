@@ -0,0 +1,8 @@
+use tag_password::{HashProof, Hashed, Password};
+
+fn main() {
+    let hashed: Password<Hashed> = "$argon2id$v=19$m=8,t=1,p=1$dGFnX3Bhc3N3b3JkX3NhbHQ$VSGjAZoWLd8Ds9tkbDEx49KABqrdxsMoISbYU31PMbM"
+        .parse()
+        .expect("Parsing should not fail.");
+    let _proof = HashProof { hashed };
+}
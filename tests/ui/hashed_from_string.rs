@@ -0,0 +1,5 @@
+use tag_password::{Hashed, Password};
+
+fn main() {
+    let _hashed_password = Password::<Hashed>::from(String::from("not a hash"));
+}